@@ -3,13 +3,33 @@ extern crate rustc_version;
 #[cfg(feature = "generate-parsing-rs")]
 extern crate peg;
 
-use rustc_version::{version_meta, Channel};
+use rustc_version::{version, version_meta, Channel};
+
+/// Enables the forwarding of trait methods that were stabilized after the oldest supported Rust
+/// version, when the compiler is new enough to have them
+fn rustc_version_cfgs() {
+    let version = version().unwrap();
+    let cfgs = [
+        ((1, 36), "derive_more_io_vectored"),
+        ((1, 51), "derive_more_stream_position"),
+        ((1, 55), "derive_more_rewind"),
+        ((1, 80), "derive_more_seek_relative"),
+        ((1, 83), "derive_more_skip_until"),
+    ];
+    for &(stabilized, cfg) in &cfgs {
+        println!("cargo:rustc-check-cfg=cfg({})", cfg);
+        if (version.major, version.minor) >= stabilized {
+            println!("cargo:rustc-cfg={}", cfg);
+        }
+    }
+}
 
 #[cfg(not(feature = "generate-parsing-rs"))]
 fn main() {
     if version_meta().unwrap().channel == Channel::Nightly {
         println!("cargo:rustc-cfg=feature=\"nightly\"");
     }
+    rustc_version_cfgs();
 }
 
 #[cfg(feature = "generate-parsing-rs")]
@@ -17,6 +37,7 @@ fn main() {
     if version_meta().unwrap().channel == Channel::Nightly {
         println!("cargo:rustc-cfg=feature=\"nightly\"");
     }
+    rustc_version_cfgs();
 
     let contents = match ::std::fs::read_to_string("src/parsing.rustpeg") {
         Ok(contents) => contents,
@@ -23,12 +23,22 @@ pub struct MyInt(i32);
 
 /// Some docs
 #[derive(Deref, DerefMut)]
+#[deref(forward)]
 pub struct MyBoxedInt(Box<i32>);
 
 /// Some docs
 #[derive(Index, IndexMut)]
 pub struct MyVec(Vec<i32>);
 
+/// Some docs
+#[derive(Getters)]
+#[getter(mut)]
+pub struct MyConfig {
+    /// Some docs
+    name: String,
+    port: u16,
+}
+
 /// Some docs
 #[allow(dead_code)]
 #[derive(Clone, Copy, TryInto)]
@@ -1,10 +1,13 @@
 use crate::add_helpers::{struct_exprs, tuple_exprs};
-use crate::utils::{add_extra_ty_param_bound_op, named_to_vec, unnamed_to_vec};
+use crate::utils::{
+    add_extra_ty_param_bound_op, get_skipped_fields, get_skipped_type_params, named_to_vec,
+    unnamed_to_vec,
+};
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{Data, DeriveInput, Fields, Ident};
+use syn::{parse::Result, Data, DeriveInput, Fields, Ident};
 
-pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
     let trait_ident = Ident::new(trait_name, Span::call_site());
     let method_name = trait_name.to_string();
     #[allow(deprecated)]
@@ -13,20 +16,43 @@ pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
     let method_ident = Ident::new(&(method_name.to_string() + "_assign"), Span::call_site());
     let input_type = &input.ident;
 
-    let generics = add_extra_ty_param_bound_op(&input.generics, &trait_ident);
-    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
-
-    let exprs = match input.data {
+    let (exprs, fields, skipped) = match input.data {
         Data::Struct(ref data_struct) => match data_struct.fields {
-            Fields::Unnamed(ref fields) => tuple_exprs(&unnamed_to_vec(fields), &method_ident),
-            Fields::Named(ref fields) => struct_exprs(&named_to_vec(fields), &method_ident),
+            Fields::Unnamed(ref fields) => {
+                let field_vec = unnamed_to_vec(fields);
+                let skipped = get_skipped_fields(&field_vec, "add")?;
+                (
+                    tuple_exprs(&field_vec, &skipped, &method_ident),
+                    field_vec,
+                    skipped,
+                )
+            }
+            Fields::Named(ref fields) => {
+                let field_vec = named_to_vec(fields);
+                let skipped = get_skipped_fields(&field_vec, "add")?;
+                (
+                    struct_exprs(&field_vec, &skipped, &method_ident),
+                    field_vec,
+                    skipped,
+                )
+            }
             _ => panic!(format!("Unit structs cannot use derive({})", trait_name)),
         },
 
         _ => panic!(format!("Only structs can use derive({})", trait_name)),
     };
+    // Skipped fields are left as they are
+    let exprs = exprs
+        .into_iter()
+        .zip(&skipped)
+        .filter(|(_, &skip)| !skip)
+        .map(|(expr, _)| expr);
+
+    let skipped_params = get_skipped_type_params(&input.generics, &fields, &skipped);
+    let generics = add_extra_ty_param_bound_op(&input.generics, &trait_ident, &skipped_params);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    quote!(
+    Ok(quote!(
         impl#impl_generics ::core::ops::#trait_ident for #input_type#ty_generics #where_clause {
             #[inline]
             fn #method_ident(&mut self, rhs: #input_type#ty_generics) {
@@ -34,5 +60,5 @@ pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
                   )*
             }
         }
-    )
+    ))
 }
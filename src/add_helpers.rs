@@ -2,26 +2,34 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{Field, Ident, Index};
 
-pub fn tuple_exprs(fields: &[&Field], method_ident: &Ident) -> Vec<TokenStream> {
+pub fn tuple_exprs(fields: &[&Field], skipped: &[bool], method_ident: &Ident) -> Vec<TokenStream> {
     let mut exprs = vec![];
 
-    for i in 0..fields.len() {
+    for (i, (_, &skip)) in fields.iter().zip(skipped).enumerate() {
         let i = Index::from(i);
-        // generates `self.0.add(rhs.0)`
-        let expr = quote!(self.#i.#method_ident(rhs.#i));
+        // generates `self.0.add(rhs.0)`, or `self.0` for skipped fields
+        let expr = if skip {
+            quote!(self.#i)
+        } else {
+            quote!(self.#i.#method_ident(rhs.#i))
+        };
         exprs.push(expr);
     }
     exprs
 }
 
-pub fn struct_exprs(fields: &[&Field], method_ident: &Ident) -> Vec<TokenStream> {
+pub fn struct_exprs(fields: &[&Field], skipped: &[bool], method_ident: &Ident) -> Vec<TokenStream> {
     let mut exprs = vec![];
 
-    for field in fields {
+    for (field, &skip) in fields.iter().zip(skipped) {
         // It's safe to unwrap because struct fields always have an identifier
         let field_id = field.ident.as_ref().unwrap();
-        // generates `x: self.x.add(rhs.x)`
-        let expr = quote!(self.#field_id.#method_ident(rhs.#field_id));
+        // generates `x: self.x.add(rhs.x)`, or `x: self.x` for skipped fields
+        let expr = if skip {
+            quote!(self.#field_id)
+        } else {
+            quote!(self.#field_id.#method_ident(rhs.#field_id))
+        };
         exprs.push(expr)
     }
     exprs
@@ -1,37 +1,45 @@
 use crate::add_helpers::{struct_exprs, tuple_exprs};
 use crate::utils::{
-    add_extra_type_param_bound_op_output, field_idents, named_to_vec, numbered_vars, unnamed_to_vec,
+    add_extra_type_param_bound_op_output, field_idents, get_skipped_fields,
+    get_skipped_type_params, named_to_vec, numbered_vars, unnamed_to_vec,
 };
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
-use std::iter;
-use syn::{Data, DataEnum, DeriveInput, Field, Fields, Ident};
+use syn::{parse::Result, Data, DataEnum, DeriveInput, Field, Fields, Ident};
 
-pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
     let trait_name = trait_name.trim_end_matches("Self");
     let trait_ident = Ident::new(trait_name, Span::call_site());
     let method_name = trait_name.to_lowercase();
     let method_ident = Ident::new(&method_name, Span::call_site());
     let input_type = &input.ident;
 
-    let generics = add_extra_type_param_bound_op_output(&input.generics, &trait_ident);
+    let fields: Vec<&Field> = match input.data {
+        Data::Struct(ref data_struct) => data_struct.fields.iter().collect(),
+        Data::Enum(ref data_enum) => data_enum.variants.iter().flat_map(|v| &v.fields).collect(),
+        _ => vec![],
+    };
+    let skipped = get_skipped_fields(&fields, "add")?;
+    let skipped_params = get_skipped_type_params(&input.generics, &fields, &skipped);
+    let generics =
+        add_extra_type_param_bound_op_output(&input.generics, &trait_ident, &skipped_params);
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let (output_type, block) = match input.data {
         Data::Struct(ref data_struct) => match data_struct.fields {
-            Fields::Unnamed(ref fields) => (
+            Fields::Unnamed(_) => (
                 quote!(#input_type#ty_generics),
-                tuple_content(input_type, &unnamed_to_vec(fields), &method_ident),
+                tuple_content(input_type, &fields, &skipped, &method_ident),
             ),
-            Fields::Named(ref fields) => (
+            Fields::Named(_) => (
                 quote!(#input_type#ty_generics),
-                struct_content(input_type, &named_to_vec(fields), &method_ident),
+                struct_content(input_type, &fields, &skipped, &method_ident),
             ),
             _ => panic!(format!("Unit structs cannot use derive({})", trait_name)),
         },
         Data::Enum(ref data_enum) => (
             quote!(::core::result::Result<#input_type#ty_generics, &'static str>),
-            enum_content(input_type, data_enum, &method_ident),
+            enum_content(input_type, data_enum, &method_ident)?,
         ),
 
         _ => panic!(format!(
@@ -40,7 +48,7 @@ pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
         )),
     };
 
-    quote!(
+    Ok(quote!(
         impl#impl_generics ::core::ops::#trait_ident for #input_type#ty_generics #where_clause {
             type Output = #output_type;
             #[inline]
@@ -48,34 +56,45 @@ pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
                 #block
             }
         }
-    )
+    ))
 }
 
 fn tuple_content<T: ToTokens>(
     input_type: &T,
     fields: &[&Field],
+    skipped: &[bool],
     method_ident: &Ident,
 ) -> TokenStream {
-    let exprs = tuple_exprs(fields, method_ident);
+    let exprs = tuple_exprs(fields, skipped, method_ident);
     quote!(#input_type(#(#exprs),*))
 }
 
-fn struct_content(input_type: &Ident, fields: &[&Field], method_ident: &Ident) -> TokenStream {
+fn struct_content(
+    input_type: &Ident,
+    fields: &[&Field],
+    skipped: &[bool],
+    method_ident: &Ident,
+) -> TokenStream {
     // It's safe to unwrap because struct fields always have an identifier
-    let exprs = struct_exprs(fields, method_ident);
+    let exprs = struct_exprs(fields, skipped, method_ident);
     let field_names = field_idents(fields);
 
     quote!(#input_type{#(#field_names: #exprs),*})
 }
 
 #[allow(clippy::cognitive_complexity)]
-fn enum_content(input_type: &Ident, data_enum: &DataEnum, method_ident: &Ident) -> TokenStream {
+fn enum_content(
+    input_type: &Ident,
+    data_enum: &DataEnum,
+    method_ident: &Ident,
+) -> Result<TokenStream> {
     let mut matches = vec![];
-    let mut method_iter = iter::repeat(method_ident);
 
     for variant in &data_enum.variants {
         let subtype = &variant.ident;
         let subtype = quote!(#input_type::#subtype);
+        let field_vec: Vec<_> = variant.fields.iter().collect();
+        let skipped = get_skipped_fields(&field_vec, "add")?;
 
         match variant.fields {
             Fields::Unnamed(ref fields) => {
@@ -83,12 +102,11 @@ fn enum_content(input_type: &Ident, data_enum: &DataEnum, method_ident: &Ident)
                 // (Subtype(left_vars), TypePath(right_vars)) => Ok(TypePath(exprs))
                 let size = unnamed_to_vec(fields).len();
                 let l_vars = &numbered_vars(size, "l_");
-                let r_vars = &numbered_vars(size, "r_");
-                let method_iter = method_iter.by_ref();
+                let (r_pats, exprs) = variant_exprs(l_vars, &skipped, method_ident);
                 let matcher = quote! {
                     (#subtype(#(#l_vars),*),
-                     #subtype(#(#r_vars),*)) => {
-                        ::core::result::Result::Ok(#subtype(#(#l_vars.#method_iter(#r_vars)),*))
+                     #subtype(#(#r_pats),*)) => {
+                        ::core::result::Result::Ok(#subtype(#(#exprs),*))
                     }
                 };
                 matches.push(matcher);
@@ -102,12 +120,11 @@ fn enum_content(input_type: &Ident, data_enum: &DataEnum, method_ident: &Ident)
                 let size = field_vec.len();
                 let field_names = &field_idents(&field_vec);
                 let l_vars = &numbered_vars(size, "l_");
-                let r_vars = &numbered_vars(size, "r_");
-                let method_iter = method_iter.by_ref();
+                let (r_pats, exprs) = variant_exprs(l_vars, &skipped, method_ident);
                 let matcher = quote! {
                     (#subtype{#(#field_names: #l_vars),*},
-                     #subtype{#(#field_names: #r_vars),*}) => {
-                        ::core::result::Result::Ok(#subtype{#(#field_names: #l_vars.#method_iter(#r_vars)),*})
+                     #subtype{#(#field_names: #r_pats),*}) => {
+                        ::core::result::Result::Ok(#subtype{#(#field_names: #exprs),*})
                     }
                 };
                 matches.push(matcher);
@@ -128,9 +145,32 @@ fn enum_content(input_type: &Ident, data_enum: &DataEnum, method_ident: &Ident)
         );
         matches.push(quote!(_ => ::core::result::Result::Err(#message)));
     }
-    quote!(
+    Ok(quote!(
         match (self, rhs) {
             #(#matches),*
         }
-    )
+    ))
+}
+
+/// Returns the patterns for the fields of the right hand side of a variant, and the expressions
+/// that combine them with the fields of the left hand side. Skipped fields are taken from the
+/// left hand side, so they're not bound on the right hand side.
+fn variant_exprs(
+    l_vars: &[Ident],
+    skipped: &[bool],
+    method_ident: &Ident,
+) -> (Vec<TokenStream>, Vec<TokenStream>) {
+    let r_vars = numbered_vars(l_vars.len(), "r_");
+    l_vars
+        .iter()
+        .zip(r_vars)
+        .zip(skipped)
+        .map(|((l_var, r_var), &skip)| {
+            if skip {
+                (quote!(_), quote!(#l_var))
+            } else {
+                (quote!(#r_var), quote!(#l_var.#method_ident(#r_var)))
+            }
+        })
+        .unzip()
 }
@@ -0,0 +1,220 @@
+use crate::utils::{
+    add_extra_generic_param, add_extra_where_clauses, field_member, get_attr_args,
+    get_common_field_type, has_ordered_repr, is_flag,
+};
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{Error, Result},
+    spanned::Spanned,
+    Data, DeriveInput, Field, Fields, Ident, Member,
+};
+
+/// Provides the hook to expand `#[derive(AsRef)]` and `#[derive(AsMut)]` into an implementation of
+/// `AsRef` or `AsMut` respectively
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
+    let (attr_name, method_ident, reference) = match trait_name {
+        "AsRef" => ("as_ref", Ident::new("as_ref", Span::call_site()), quote!(&)),
+        "AsMut" => (
+            "as_mut",
+            Ident::new("as_mut", Span::call_site()),
+            quote!(&mut),
+        ),
+        _ => unreachable!(),
+    };
+    let trait_ident = Ident::new(trait_name, Span::call_site());
+    let trait_path = &quote!(::core::convert::#trait_ident);
+
+    let fields = match input.data {
+        Data::Struct(ref data_struct) => &data_struct.fields,
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                format!("Only structs can derive({})", trait_name),
+            ))
+        }
+    };
+
+    let container_args = get_attr_args(&input.attrs, attr_name)?;
+    if let Some([arg]) = container_args.as_deref() {
+        if is_flag(arg, "array") {
+            return array_impl(
+                input,
+                fields,
+                trait_name,
+                attr_name,
+                &method_ident,
+                &reference,
+            );
+        }
+    }
+    let container_forward = match container_args {
+        Some(args) => {
+            if !parse_args(&args, attr_name)? {
+                return Err(Error::new(
+                    input.span(),
+                    format!(
+                        "Proper syntax: #[{}(forward)] or #[{}(array)] on a struct, or #[{}] on its fields",
+                        attr_name, attr_name, attr_name
+                    ),
+                ));
+            }
+            if fields.iter().count() != 1 {
+                return Err(Error::new(
+                    input.span(),
+                    format!(
+                        "#[{}(forward)] on a struct requires it to have exactly one field",
+                        attr_name
+                    ),
+                ));
+            }
+            true
+        }
+        None => false,
+    };
+
+    let mut selected: Vec<(Member, &Field, bool)> = vec![];
+    for (i, field) in fields.iter().enumerate() {
+        if let Some(args) = get_attr_args(&field.attrs, attr_name)? {
+            let forward = parse_args(&args, attr_name)?;
+            selected.push((field_member(i, field), field, forward));
+        }
+    }
+    if container_forward {
+        let field = fields.iter().next().unwrap();
+        selected = vec![(field_member(0, field), field, true)];
+    } else if selected.is_empty() {
+        let mut it = fields.iter();
+        match (it.next(), it.next()) {
+            (Some(field), None) => selected.push((field_member(0, field), field, false)),
+            _ => {
+                return Err(Error::new(
+                    fields.span(),
+                    format!(
+                        "#[derive({})] requires exactly one field or at least one field marked with #[{}]",
+                        trait_name, attr_name
+                    ),
+                ))
+            }
+        }
+    }
+    if selected.len() > 1 {
+        if let Some((_, field, _)) = selected.iter().find(|(_, _, forward)| *forward) {
+            return Err(Error::new(
+                field.span(),
+                format!(
+                    "#[{}(forward)] cannot be combined with other fields marked with #[{}]",
+                    attr_name, attr_name
+                ),
+            ));
+        }
+    }
+
+    let input_type = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let mut tokens = TokenStream::new();
+    for (member, field, forward) in selected {
+        let field_type = &field.ty;
+        if forward {
+            let target = &Ident::new("__AsT", Span::call_site());
+            let casted_trait = &quote!(<#field_type as #trait_path<#target>>);
+            let generics = add_extra_generic_param(&input.generics, quote!(#target: ?Sized));
+            let generics =
+                add_extra_where_clauses(&generics, quote!(where #field_type: #trait_path<#target>));
+            let (impl_generics, _, where_clause) = generics.split_for_impl();
+            quote! {
+                impl#impl_generics #trait_path<#target> for #input_type#ty_generics #where_clause
+                {
+                    #[inline]
+                    fn #method_ident(#reference self) -> #reference #target {
+                        #casted_trait::#method_ident(#reference self.#member)
+                    }
+                }
+            }
+            .to_tokens(&mut tokens);
+        } else {
+            let (impl_generics, _, where_clause) = input.generics.split_for_impl();
+            quote! {
+                impl#impl_generics #trait_path<#field_type> for #input_type#ty_generics #where_clause
+                {
+                    #[inline]
+                    fn #method_ident(#reference self) -> #reference #field_type {
+                        #reference self.#member
+                    }
+                }
+            }
+            .to_tokens(&mut tokens);
+        }
+    }
+    Ok(tokens)
+}
+
+/// Returns whether `forward` was given.
+fn parse_args(args: &[syn::NestedMeta], attr_name: &str) -> Result<bool> {
+    match args {
+        [] => Ok(false),
+        [arg] if is_flag(arg, "forward") => Ok(true),
+        _ => Err(Error::new(
+            args[0].span(),
+            format!(
+                "Proper syntax: #[{}] or #[{}(forward)]",
+                attr_name, attr_name
+            ),
+        )),
+    }
+}
+
+/// Implements `AsRef<[T; N]>` or `AsMut<[T; N]>` for `#[repr(C)]` structs whose fields all have
+/// the same type, by reinterpreting the struct as an array.
+fn array_impl(
+    input: &DeriveInput,
+    fields: &Fields,
+    trait_name: &str,
+    attr_name: &str,
+    method_ident: &Ident,
+    reference: &TokenStream,
+) -> Result<TokenStream> {
+    let what = format!("#[{}(array)]", attr_name);
+    if !has_ordered_repr(&input.attrs) {
+        return Err(Error::new(
+            input.span(),
+            format!(
+                "{} requires #[repr(C)] or #[repr(transparent)] without packed",
+                what
+            ),
+        ));
+    }
+    let field_type = get_common_field_type(fields, input.span(), &what)?;
+    for field in fields {
+        if get_attr_args(&field.attrs, attr_name)?.is_some() {
+            return Err(Error::new(
+                field.span(),
+                format!(
+                    "#[{}] cannot be used on fields together with {}",
+                    attr_name, what
+                ),
+            ));
+        }
+    }
+    let len = fields.iter().count();
+    let trait_ident = Ident::new(trait_name, Span::call_site());
+    let input_type = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (self_ptr, array_ptr) = if trait_name == "AsMut" {
+        (quote!(*mut Self), quote!(*mut [#field_type; #len]))
+    } else {
+        (quote!(*const Self), quote!(*const [#field_type; #len]))
+    };
+    Ok(quote! {
+        impl#impl_generics ::core::convert::#trait_ident<[#field_type; #len]> for
+            #input_type#ty_generics #where_clause
+        {
+            #[inline]
+            fn #method_ident(#reference self) -> #reference [#field_type; #len] {
+                // SAFETY: The struct is `#[repr(C)]` or `#[repr(transparent)]` and all its fields
+                // have the same type, so it has the same layout as an array of that type.
+                unsafe { #reference *(self as #self_ptr as #array_ptr) }
+            }
+        }
+    })
+}
@@ -0,0 +1,127 @@
+use crate::utils::{
+    add_extra_generic_param, add_extra_where_clauses, get_attr_args, get_forwarded_field,
+    get_types_arg, is_flag,
+};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse::{Error, Result},
+    spanned::Spanned,
+    Data, DeriveInput, Ident, NestedMeta, Type,
+};
+
+/// The implementations that are generated, as selected with `#[borrow(...)]`
+enum Mode {
+    /// Only borrowing as the type of the field
+    Field,
+    /// Borrowing as every type that the field can be borrowed as
+    Forward,
+    /// Borrowing as the given types, which the field can be borrowed as
+    Types(Vec<Type>),
+}
+
+/// Provides the hook to expand `#[derive(Borrow)]` and `#[derive(BorrowMut)]` into
+/// implementations of `Borrow` or `BorrowMut` respectively, which borrow the field of a struct
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
+    let fields = match input.data {
+        Data::Struct(ref data_struct) => &data_struct.fields,
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                format!("Only structs can derive({})", trait_name),
+            ))
+        }
+    };
+    let (member, field, field_args) =
+        get_forwarded_field(fields, input.span(), trait_name, "borrow")?;
+    let mut args = get_attr_args(&input.attrs, "borrow")?.unwrap_or_default();
+    args.extend(field_args);
+    let mode = parse_args(&args)?;
+
+    let trait_ident = Ident::new(trait_name, Span::call_site());
+    let trait_path = &quote!(::core::borrow::#trait_ident);
+    let (method, reference) = if trait_name == "BorrowMut" {
+        (quote!(borrow_mut), quote!(&mut))
+    } else {
+        (quote!(borrow), quote!(&))
+    };
+    let input_type = &input.ident;
+    let field_type = &field.ty;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let impl_borrow = |generics: &syn::Generics, borrowed: &TokenStream, body: TokenStream| {
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        quote! {
+            impl#impl_generics #trait_path<#borrowed> for #input_type#ty_generics #where_clause
+            {
+                #[inline]
+                fn #method(#reference self) -> #reference #borrowed {
+                    #body
+                }
+            }
+        }
+    };
+
+    Ok(match mode {
+        Mode::Field => impl_borrow(
+            &input.generics,
+            &quote!(#field_type),
+            quote!(#reference self.#member),
+        ),
+        Mode::Forward => {
+            let borrowed = quote!(__BorrowT);
+            let generics = add_extra_where_clauses(
+                &add_extra_generic_param(&input.generics, quote!(#borrowed: ?Sized)),
+                quote!(where #field_type: #trait_path<#borrowed>),
+            );
+            impl_borrow(
+                &generics,
+                &borrowed,
+                quote!(<#field_type as #trait_path<#borrowed>>::#method(#reference self.#member)),
+            )
+        }
+        Mode::Types(types) => types
+            .iter()
+            .map(|ty| {
+                let borrowed = quote!(#ty);
+                let generics = add_extra_where_clauses(
+                    &input.generics,
+                    quote!(where #field_type: #trait_path<#borrowed>),
+                );
+                impl_borrow(
+                    &generics,
+                    &borrowed,
+                    quote!(<#field_type as #trait_path<#borrowed>>::#method(#reference self.#member)),
+                )
+            })
+            .collect(),
+    })
+}
+
+/// Parses the arguments of the `#[borrow(...)]` attributes of the struct and its field.
+fn parse_args(args: &[NestedMeta]) -> Result<Mode> {
+    let mut forward = false;
+    let mut types: Option<Vec<Type>> = None;
+    for arg in args {
+        if is_flag(arg, "forward") {
+            forward = true;
+        } else if let Some(arg_types) = get_types_arg(arg)? {
+            types.get_or_insert_with(Vec::new).extend(arg_types);
+        } else {
+            return Err(Error::new(
+                arg.span(),
+                "Proper syntax: #[borrow], #[borrow(forward)] or #[borrow(types(...))]",
+            ));
+        }
+        if forward && types.is_some() {
+            return Err(Error::new(
+                arg.span(),
+                "#[borrow(forward)] cannot be combined with #[borrow(types(...))]",
+            ));
+        }
+    }
+    Ok(match types {
+        Some(types) => Mode::Types(types),
+        None if forward => Mode::Forward,
+        None => Mode::Field,
+    })
+}
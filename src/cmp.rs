@@ -0,0 +1,312 @@
+use crate::utils::{
+    add_inferred_bounds, field_member, get_attr_args, get_str_arg, is_flag, numbered_vars,
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Error, Result},
+    spanned::Spanned,
+    Data, DeriveInput, Field, Fields, Ident, Lit, Member, Meta, NestedMeta, Path,
+};
+
+/// A field that is compared, as configured with `#[cmp(...)]`
+struct CmpField<'a> {
+    member: Member,
+    field: &'a Field,
+    /// The function that returns the key to compare instead of the field
+    by: Option<Path>,
+    order: Option<u64>,
+}
+
+/// Provides the hook to expand `#[derive(PartialEqCustom)]`, `#[derive(EqCustom)]`,
+/// `#[derive(HashCustom)]`, `#[derive(PartialOrdCustom)]` and `#[derive(OrdCustom)]` into an
+/// implementation of the corresponding trait, which ignores the fields marked with `#[cmp(skip)]`
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
+    let input_type = &input.ident;
+    let variants = match input.data {
+        Data::Struct(ref data_struct) => {
+            vec![(quote!(#input_type), parse_fields(&data_struct.fields)?)]
+        }
+        Data::Enum(ref data_enum) => data_enum
+            .variants
+            .iter()
+            .map(|variant| {
+                let variant_ident = &variant.ident;
+                Ok((
+                    quote!(#input_type::#variant_ident),
+                    parse_fields(&variant.fields)?,
+                ))
+            })
+            .collect::<Result<_>>()?,
+        Data::Union(_) => {
+            return Err(Error::new(
+                input.span(),
+                format!("Only structs and enums can derive({})", trait_name),
+            ))
+        }
+    };
+
+    let trait_path = match trait_name {
+        "PartialEqCustom" => quote!(::core::cmp::PartialEq),
+        "EqCustom" => quote!(::core::cmp::Eq),
+        "HashCustom" => quote!(::core::hash::Hash),
+        "PartialOrdCustom" => quote!(::core::cmp::PartialOrd),
+        _ => quote!(::core::cmp::Ord),
+    };
+    let body = match trait_name {
+        "PartialEqCustom" => {
+            let eq = compare_variants(
+                &variants,
+                |lhs, rhs, rest| quote!(::core::cmp::PartialEq::eq(#lhs, #rhs) && #rest),
+                &quote!(true),
+                &quote!(false),
+            );
+            quote! {
+                #[inline]
+                fn eq(&self, other: &Self) -> bool {
+                    #eq
+                }
+            }
+        }
+        // A marker trait without any methods
+        "EqCustom" => quote!(),
+        "HashCustom" => hash_body(&variants),
+        "PartialOrdCustom" => {
+            let variant_cmp = quote! {
+                ::core::cmp::PartialOrd::partial_cmp(&__self_variant, &__other_variant)
+            };
+            let partial_cmp = compare_variants(
+                &variants,
+                |lhs, rhs, rest| {
+                    quote! {
+                        match ::core::cmp::PartialOrd::partial_cmp(#lhs, #rhs) {
+                            ::core::option::Option::Some(::core::cmp::Ordering::Equal) => #rest,
+                            cmp => cmp,
+                        }
+                    }
+                },
+                &quote!(::core::option::Option::Some(::core::cmp::Ordering::Equal)),
+                &variant_cmp,
+            );
+            let variant_indices = variant_indices(&variants);
+            quote! {
+                #[inline]
+                fn partial_cmp(
+                    &self,
+                    other: &Self,
+                ) -> ::core::option::Option<::core::cmp::Ordering> {
+                    #variant_indices
+                    #partial_cmp
+                }
+            }
+        }
+        _ => {
+            let variant_cmp = quote!(::core::cmp::Ord::cmp(&__self_variant, &__other_variant));
+            let cmp = compare_variants(
+                &variants,
+                |lhs, rhs, rest| {
+                    quote! {
+                        match ::core::cmp::Ord::cmp(#lhs, #rhs) {
+                            ::core::cmp::Ordering::Equal => #rest,
+                            cmp => cmp,
+                        }
+                    }
+                },
+                &quote!(::core::cmp::Ordering::Equal),
+                &variant_cmp,
+            );
+            let variant_indices = variant_indices(&variants);
+            quote! {
+                #[inline]
+                fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                    #variant_indices
+                    #cmp
+                }
+            }
+        }
+    };
+
+    // Fields that are compared by a key don't need to implement the trait themselves.
+    let field_types = variants
+        .iter()
+        .flat_map(|(_, fields)| fields)
+        .filter(|field| field.by.is_none())
+        .map(|field| &field.field.ty);
+    let generics = add_inferred_bounds(&input.generics, field_types, &trait_path);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl#impl_generics #trait_path for #input_type#ty_generics #where_clause
+        {
+            #body
+        }
+    })
+}
+
+/// Returns the fields that are compared, in the order they are compared in. The fields with an
+/// `order` come first, and the others follow in the order they are declared in.
+fn parse_fields(fields: &Fields) -> Result<Vec<CmpField<'_>>> {
+    let proper_syntax =
+        r#"Proper syntax: #[cmp(skip)], #[cmp(by = "key_fn")] or #[cmp(order = 0)]"#;
+    let mut cmp_fields = vec![];
+    for (i, field) in fields.iter().enumerate() {
+        let args = get_attr_args(&field.attrs, "cmp")?.unwrap_or_default();
+        let mut skip = false;
+        let mut by = None;
+        let mut order = None;
+        for arg in &args {
+            if is_flag(arg, "skip") {
+                skip = true;
+            } else if let Some(path) = get_str_arg(arg, "by")? {
+                by = Some(path.parse()?);
+            } else if let Some(value) = get_order_arg(arg)? {
+                order = Some(value);
+            } else {
+                return Err(Error::new(arg.span(), proper_syntax));
+            }
+        }
+        if skip {
+            if args.len() > 1 {
+                return Err(Error::new(
+                    field.span(),
+                    "#[cmp(skip)] cannot be combined with other arguments",
+                ));
+            }
+            continue;
+        }
+        cmp_fields.push(CmpField {
+            member: field_member(i, field),
+            field,
+            by,
+            order,
+        });
+    }
+    // The sort is stable, so fields without an order keep the order they are declared in.
+    cmp_fields.sort_by_key(|field| field.order.unwrap_or(u64::MAX));
+    Ok(cmp_fields)
+}
+
+/// Returns the value of `arg` if it is `order = N`.
+fn get_order_arg(arg: &NestedMeta) -> Result<Option<u64>> {
+    match arg {
+        NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("order") => match nv.lit {
+            Lit::Int(ref int) => int.base10_parse().map(Some),
+            ref lit => Err(Error::new(
+                lit.span(),
+                "Expected an integer literal for `order`",
+            )),
+        },
+        _ => Ok(None),
+    }
+}
+
+/// Returns the pattern that binds the compared fields to the given variables.
+fn pattern(path: &TokenStream, fields: &[CmpField], vars: &[Ident]) -> TokenStream {
+    let members = fields.iter().map(|field| &field.member);
+    quote!(#path { #(#members: #vars,)* .. })
+}
+
+/// Returns a reference to the value that is compared for a field bound to `var`.
+fn value(field: &CmpField, var: &Ident) -> TokenStream {
+    match field.by {
+        Some(ref by) => quote!(&#by(#var)),
+        None => quote!(#var),
+    }
+}
+
+/// Compares two values of the same variant by chaining the comparisons of their fields with
+/// `chain`, ending with `equal`. Values of different variants are compared with `different`.
+fn compare_variants<F>(
+    variants: &[(TokenStream, Vec<CmpField>)],
+    chain: F,
+    equal: &TokenStream,
+    different: &TokenStream,
+) -> TokenStream
+where
+    F: Fn(TokenStream, TokenStream, TokenStream) -> TokenStream,
+{
+    if variants.is_empty() {
+        return quote!(match *self {});
+    }
+    let arms = variants.iter().map(|(path, fields)| {
+        let self_vars = numbered_vars(fields.len(), "self_");
+        let other_vars = numbered_vars(fields.len(), "other_");
+        let self_pattern = pattern(path, fields, &self_vars);
+        let other_pattern = pattern(path, fields, &other_vars);
+        let comparison = fields
+            .iter()
+            .zip(self_vars.iter().zip(&other_vars))
+            .rev()
+            .fold(equal.clone(), |rest, (field, (self_var, other_var))| {
+                chain(value(field, self_var), value(field, other_var), rest)
+            });
+        quote!((#self_pattern, #other_pattern) => #comparison,)
+    });
+    let different_arm = if variants.len() > 1 {
+        quote!(_ => #different,)
+    } else {
+        quote!()
+    };
+    quote! {
+        match (self, other) {
+            #(#arms)*
+            #different_arm
+        }
+    }
+}
+
+/// Returns the statements that store the positions of the variants of `self` and `other`, which
+/// are compared when the variants are different.
+fn variant_indices(variants: &[(TokenStream, Vec<CmpField>)]) -> TokenStream {
+    if variants.len() < 2 {
+        return quote!();
+    }
+    let arms: Vec<_> = variants
+        .iter()
+        .enumerate()
+        .map(|(i, (path, _))| quote!(#path { .. } => #i,))
+        .collect();
+    quote! {
+        let __self_variant: usize = match self {
+            #(#arms)*
+        };
+        let __other_variant: usize = match other {
+            #(#arms)*
+        };
+    }
+}
+
+/// Hashes the compared fields, and the variant for enums with multiple variants.
+fn hash_body(variants: &[(TokenStream, Vec<CmpField>)]) -> TokenStream {
+    if variants.is_empty() {
+        return quote! {
+            #[inline]
+            fn hash<__HasherT: ::core::hash::Hasher>(&self, state: &mut __HasherT) {
+                match *self {}
+            }
+        };
+    }
+    let discriminant = if variants.len() > 1 {
+        quote!(::core::hash::Hash::hash(&::core::mem::discriminant(self), state);)
+    } else {
+        quote!()
+    };
+    let arms = variants.iter().map(|(path, fields)| {
+        let vars = numbered_vars(fields.len(), "self_");
+        let pattern = pattern(path, fields, &vars);
+        let values = fields
+            .iter()
+            .zip(&vars)
+            .map(|(field, var)| value(field, var));
+        quote!(#pattern => { #(::core::hash::Hash::hash(#values, state);)* })
+    });
+    quote! {
+        #[inline]
+        fn hash<__HasherT: ::core::hash::Hasher>(&self, state: &mut __HasherT) {
+            #discriminant
+            match self {
+                #(#arms)*
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use crate::utils::{add_extra_generic_param, add_extra_where_clauses, get_forwarded_field};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Error, Result},
+    spanned::Spanned,
+    Data, DeriveInput, GenericArgument, Generics, PathArguments, Type,
+};
+
+/// Provides the hook to expand `#[derive(PartialEqInner)]` and `#[derive(PartialOrdInner)]` into
+/// implementations of `PartialEq` or `PartialOrd` respectively, which compare a struct with the
+/// type of its field in both directions
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
+    let fields = match input.data {
+        Data::Struct(ref data_struct) => &data_struct.fields,
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                format!("Only structs can derive({})", trait_name),
+            ))
+        }
+    };
+    let (member, field, args) = get_forwarded_field(fields, input.span(), trait_name, "cmp_inner")?;
+    if let Some(arg) = args.first() {
+        return Err(Error::new(arg.span(), "Proper syntax: #[cmp_inner]"));
+    }
+    let field_type = &field.ty;
+
+    let mut tokens = TokenStream::new();
+    let (trait_path, method, output) = trait_method(trait_name);
+    let input_type = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let mut impl_cmp = |generics: &Generics, lhs: &TokenStream, rhs: &TokenStream, body| {
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        tokens.extend(quote! {
+            impl#impl_generics #trait_path<#rhs> for #lhs #where_clause
+            {
+                #[inline]
+                fn #method(&self, other: &#rhs) -> #output {
+                    #body
+                }
+            }
+        });
+    };
+    let self_type = &quote!(#input_type#ty_generics);
+    let inner_type = &quote!(#field_type);
+
+    let generics = add_extra_where_clauses(
+        &input.generics,
+        quote!(where #field_type: #trait_path<#field_type>),
+    );
+    impl_cmp(
+        &generics,
+        self_type,
+        inner_type,
+        quote!(<#field_type as #trait_path>::#method(&self.#member, other)),
+    );
+    // Implementing a foreign trait for a type parameter isn't allowed, even if the trait is
+    // generic over the struct. References and boxes don't cover the type parameter either.
+    if !has_uncovered_type_param(field_type, &input.generics) {
+        impl_cmp(
+            &generics,
+            inner_type,
+            self_type,
+            quote!(<#field_type as #trait_path>::#method(self, &other.#member)),
+        );
+    }
+
+    // `String` can't be compared with `str` by `PartialOrd`, so these are compared as `str`.
+    if is_string(field_type) {
+        let str_trait = quote!(<str as #trait_path>);
+        let str_type = &quote!(str);
+        impl_cmp(
+            &input.generics,
+            self_type,
+            str_type,
+            quote!(#str_trait::#method(&self.#member, other)),
+        );
+        impl_cmp(
+            &input.generics,
+            str_type,
+            self_type,
+            quote!(#str_trait::#method(self, &other.#member)),
+        );
+        let lifetime = quote!('__deriveMoreLifetime);
+        let generics = add_extra_generic_param(&input.generics, lifetime.clone());
+        let str_ref_type = &quote!(&#lifetime str);
+        impl_cmp(
+            &generics,
+            self_type,
+            str_ref_type,
+            quote!(#str_trait::#method(&self.#member, *other)),
+        );
+        impl_cmp(
+            &generics,
+            str_ref_type,
+            self_type,
+            quote!(#str_trait::#method(*self, &other.#member)),
+        );
+    }
+    Ok(tokens)
+}
+
+/// Returns the path to the trait, and the method and its return type
+fn trait_method(trait_name: &str) -> (TokenStream, TokenStream, TokenStream) {
+    if trait_name == "PartialOrdInner" {
+        (
+            quote!(::core::cmp::PartialOrd),
+            quote!(partial_cmp),
+            quote!(::core::option::Option<::core::cmp::Ordering>),
+        )
+    } else {
+        (quote!(::core::cmp::PartialEq), quote!(eq), quote!(bool))
+    }
+}
+
+/// Returns whether `ty` is `String`, which can also be compared with `str` and `&str`.
+fn is_string(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => match path.path.segments.last() {
+            Some(segment) => segment.ident == "String" && segment.arguments.is_empty(),
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+/// Returns whether `ty` is one of the type parameters of `generics`, or one behind the fundamental
+/// types `&`, `&mut`, `Box` or `Pin`, which the orphan rules look through.
+fn has_uncovered_type_param(ty: &Type, generics: &Generics) -> bool {
+    match ty {
+        Type::Reference(reference) => has_uncovered_type_param(&reference.elem, generics),
+        Type::Paren(paren) => has_uncovered_type_param(&paren.elem, generics),
+        Type::Group(group) => has_uncovered_type_param(&group.elem, generics),
+        Type::Path(path) if path.qself.is_none() => {
+            if generics
+                .type_params()
+                .any(|param| path.path.is_ident(&param.ident))
+            {
+                return true;
+            }
+            let segment = match path.path.segments.last() {
+                Some(segment) if segment.ident == "Box" || segment.ident == "Pin" => segment,
+                _ => return false,
+            };
+            match segment.arguments {
+                PathArguments::AngleBracketed(ref args) => args.args.iter().any(|arg| match arg {
+                    GenericArgument::Type(ty) => has_uncovered_type_param(ty, generics),
+                    _ => false,
+                }),
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
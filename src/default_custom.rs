@@ -0,0 +1,155 @@
+use crate::utils::{add_extra_where_clauses, add_inferred_bounds, field_member, get_str_arg};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Error, Result},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    token::Comma,
+    Attribute, Data, DeriveInput, Meta, Type, WherePredicate,
+};
+
+/// Provides the hook to expand `#[derive(DefaultCustom)]` into an implementation of `Default`,
+/// which uses the values given with `#[default(...)]` for the fields
+pub fn expand(input: &DeriveInput, _: &str) -> Result<TokenStream> {
+    let input_type = &input.ident;
+    let (path, fields) = match input.data {
+        Data::Struct(ref data_struct) => (quote!(#input_type), &data_struct.fields),
+        Data::Enum(ref data_enum) => {
+            let mut default_variant = None;
+            for variant in &data_enum.variants {
+                if has_default_marker(&variant.attrs)? {
+                    if default_variant.is_some() {
+                        return Err(Error::new(
+                            variant.span(),
+                            "Only a single variant can be marked with #[default]",
+                        ));
+                    }
+                    default_variant = Some(variant);
+                } else if let Some(field) = variant
+                    .fields
+                    .iter()
+                    .find(|field| field.attrs.iter().any(|a| a.path.is_ident("default")))
+                {
+                    return Err(Error::new(
+                        field.span(),
+                        "#[default(...)] can only be used on the fields of the default variant",
+                    ));
+                }
+            }
+            match default_variant {
+                Some(variant) => {
+                    let variant_ident = &variant.ident;
+                    (quote!(#input_type::#variant_ident), &variant.fields)
+                }
+                None => return Err(Error::new(
+                    input.span(),
+                    "#[derive(DefaultCustom)] on an enum requires a variant marked with #[default]",
+                )),
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new(
+                input.span(),
+                "Only structs and enums can derive(DefaultCustom)",
+            ))
+        }
+    };
+
+    let mut initializers = vec![];
+    let mut defaulted_types: Vec<&Type> = vec![];
+    for (i, field) in fields.iter().enumerate() {
+        let member = field_member(i, field);
+        let value = match field_value(&field.attrs)? {
+            Some(value) => value,
+            None => {
+                defaulted_types.push(&field.ty);
+                quote!(::core::default::Default::default())
+            }
+        };
+        initializers.push(quote!(#member: #value));
+    }
+
+    let trait_path = quote!(::core::default::Default);
+    let generics = match container_bound(&input.attrs)? {
+        Some(ref predicates) if predicates.is_empty() => input.generics.clone(),
+        Some(predicates) => add_extra_where_clauses(&input.generics, quote!(where #predicates)),
+        None => add_inferred_bounds(&input.generics, defaulted_types, &trait_path),
+    };
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl#impl_generics #trait_path for #input_type#ty_generics #where_clause
+        {
+            #[inline]
+            fn default() -> Self {
+                #path { #(#initializers),* }
+            }
+        }
+    })
+}
+
+/// Returns whether a variant is marked with `#[default]`.
+fn has_default_marker(attrs: &[Attribute]) -> Result<bool> {
+    let mut marked = false;
+    for attr in attrs.iter().filter(|a| a.path.is_ident("default")) {
+        if !attr.tokens.is_empty() {
+            return Err(Error::new(
+                attr.span(),
+                "Proper syntax: #[default] on the default variant",
+            ));
+        }
+        marked = true;
+    }
+    Ok(marked)
+}
+
+/// Returns the value of a field given with `#[default(expr)]` or `#[default = "..."]`. The
+/// latter is converted into the type of the field with `Into`.
+fn field_value(attrs: &[Attribute]) -> Result<Option<TokenStream>> {
+    let mut value = None;
+    for attr in attrs.iter().filter(|a| a.path.is_ident("default")) {
+        if value.is_some() {
+            return Err(Error::new(attr.span(), "Too many default attributes given"));
+        }
+        // Arbitrary expressions can't be parsed as `Meta`, so only the `name = value` form is.
+        // Otherwise the tokens are used as the expression as they are.
+        value = Some(match attr.parse_meta() {
+            Ok(Meta::NameValue(nv)) => {
+                let lit = nv.lit;
+                quote!(::core::convert::Into::into(#lit))
+            }
+            _ => {
+                let expr: TokenStream = attr.parse_args()?;
+                if expr.is_empty() {
+                    return Err(Error::new(
+                        attr.span(),
+                        r#"Proper syntax: #[default(expr)] or #[default = "..."]"#,
+                    ));
+                }
+                expr
+            }
+        });
+    }
+    Ok(value)
+}
+
+/// Returns the bounds given with `#[default(bound = "...")]` on the type, if any. These replace
+/// the inferred bounds, so an empty string removes them.
+fn container_bound(attrs: &[Attribute]) -> Result<Option<Punctuated<WherePredicate, Comma>>> {
+    let proper_syntax = r#"Proper syntax: #[default(bound = "T: MyTrait")]"#;
+    let mut bound = None;
+    for attr in attrs.iter().filter(|a| a.path.is_ident("default")) {
+        let nested = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list.nested,
+            _ => return Err(Error::new(attr.span(), proper_syntax)),
+        };
+        for arg in &nested {
+            match get_str_arg(arg, "bound")? {
+                Some(lit) => bound = Some(lit.parse_with(Punctuated::parse_terminated)?),
+                None => return Err(Error::new(arg.span(), proper_syntax)),
+            }
+        }
+    }
+    Ok(bound)
+}
@@ -1,65 +1,119 @@
-use crate::utils::{add_extra_ty_param_bound, named_to_vec, unnamed_to_vec};
+use crate::utils::{
+    add_extra_where_clauses, get_attr_args, get_forwarded_field, get_variant_forwarded_fields,
+    is_flag, variant_field_match,
+};
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{Data, DeriveInput, Field, Fields, Ident};
+use syn::{
+    parse::{Error, Result},
+    spanned::Spanned,
+    Data, DeriveInput, Ident, NestedMeta,
+};
 
-/// Provides the hook to expand `#[derive(Index)]` into an implementation of `From`
-pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
+/// Provides the hook to expand `#[derive(Deref)]` and `#[derive(DerefMut)]` into an
+/// implementation of `Deref` or `DerefMut` respectively
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
     let trait_ident = Ident::new(trait_name, Span::call_site());
     let trait_path = &quote!(::core::ops::#trait_ident);
     let input_type = &input.ident;
-    let field_vec: Vec<&Field>;
-    let member = match input.data {
-        Data::Struct(ref data_struct) => match data_struct.fields {
-            Fields::Unnamed(ref fields) => {
-                field_vec = unnamed_to_vec(fields);
-                tuple_from_str(trait_name, &field_vec)
+    let container_forward = match get_attr_args(&input.attrs, "deref")? {
+        Some(args) => parse_args(&args)?,
+        None => false,
+    };
+    let (field_type, field_ref, field_mut, forward) = match input.data {
+        Data::Struct(ref data_struct) => {
+            let (member, field, args) =
+                get_forwarded_field(&data_struct.fields, input.span(), trait_name, "deref")?;
+            (
+                &field.ty,
+                quote!(&self.#member),
+                quote!(&mut self.#member),
+                container_forward || parse_args(&args)?,
+            )
+        }
+        Data::Enum(ref data_enum) => {
+            let (field_type, forwarded) =
+                get_variant_forwarded_fields(data_enum, input.span(), trait_name, "deref")?;
+            let mut forwarding_variants = 0;
+            for (_, _, args) in &forwarded {
+                if parse_args(args)? {
+                    forwarding_variants += 1;
+                }
             }
-            Fields::Named(ref fields) => {
-                field_vec = named_to_vec(fields);
-                struct_from_str(trait_name, &field_vec)
+            if forwarding_variants != 0 && forwarding_variants != forwarded.len() {
+                return Err(Error::new(
+                    input.span(),
+                    "#[deref(forward)] has to be used on the fields of all variants, or on the enum",
+                ));
             }
-            Fields::Unit => panic_one_field(trait_name),
-        },
-        _ => panic_one_field(trait_name),
+            let field_match = variant_field_match(input_type, &forwarded);
+            (
+                field_type,
+                field_match.clone(),
+                field_match,
+                container_forward || forwarding_variants != 0,
+            )
+        }
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                format!("Only structs and enums can derive({})", trait_name),
+            ))
+        }
     };
-    let field_type = &field_vec[0].ty;
 
-    let generics = add_extra_ty_param_bound(&input.generics, trait_path);
-    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
-    // let generics = add_extra_ty_param_bound(&input.generics, trait_path);
+    let generics = if forward {
+        add_extra_where_clauses(&input.generics, quote!(where #field_type: #trait_path))
+    } else {
+        input.generics.clone()
+    };
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
     let casted_trait = &quote!(<#field_type as #trait_path>);
-    quote! {
-        impl#impl_generics #trait_path for #input_type#ty_generics #where_clause
-        {
+    let body = match (trait_name, forward) {
+        ("DerefMut", true) => quote! {
+            #[inline]
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                #casted_trait::deref_mut(#field_mut)
+            }
+        },
+        ("DerefMut", false) => quote! {
+            #[inline]
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                #field_mut
+            }
+        },
+        (_, true) => quote! {
             type Target = #casted_trait::Target;
             #[inline]
             fn deref(&self) -> &Self::Target {
-                #casted_trait::deref(&#member)
+                #casted_trait::deref(#field_ref)
             }
-        }
-    }
-}
-
-fn panic_one_field(trait_name: &str) -> ! {
-    panic!(format!(
-        "Only structs with one field can derive({})",
-        trait_name
-    ))
-}
-
-fn tuple_from_str<'a>(trait_name: &str, fields: &[&'a Field]) -> (TokenStream) {
-    if fields.len() != 1 {
-        panic_one_field(trait_name)
+        },
+        (_, false) => quote! {
+            type Target = #field_type;
+            #[inline]
+            fn deref(&self) -> &Self::Target {
+                #field_ref
+            }
+        },
     };
-    quote!(self.0)
+    Ok(quote! {
+        impl#impl_generics #trait_path for #input_type#ty_generics #where_clause
+        {
+            #body
+        }
+    })
 }
 
-fn struct_from_str<'a>(trait_name: &str, fields: &[&'a Field]) -> TokenStream {
-    if fields.len() != 1 {
-        panic_one_field(trait_name)
-    };
-    let field = &fields[0];
-    let field_ident = &field.ident;
-    quote!(self.#field_ident)
+/// Returns whether `forward` was given.
+fn parse_args(args: &[NestedMeta]) -> Result<bool> {
+    match args {
+        [] => Ok(false),
+        [arg] if is_flag(arg, "forward") => Ok(true),
+        _ => Err(Error::new(
+            args[0].span(),
+            "Proper syntax: #[deref] or #[deref(forward)]",
+        )),
+    }
 }
@@ -3,13 +3,13 @@ use std::{
     fmt::Display,
 };
 
-use crate::utils::add_extra_where_clauses;
+use crate::utils::{add_extra_where_clauses, get_matcher, unraw};
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, quote_spanned};
 use syn::{
     parse::{Error, Result},
     spanned::Spanned,
-    Attribute, Data, DeriveInput, Fields, Lit, Meta, MetaNameValue, NestedMeta, Path, Type,
+    Attribute, Data, DeriveInput, Fields, Lit, LitStr, Meta, MetaNameValue, NestedMeta, Path, Type,
 };
 
 /// Provides the hook to expand `#[derive(Display)]` into an implementation of `From`
@@ -35,13 +35,24 @@ pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
         .map(|t| t.ident.clone())
         .collect();
 
-    let (arms, bounds) = State {
+    let state = State {
         trait_path,
         trait_attr,
         input,
         type_params,
+    };
+    let (arms, bounds) = state.get_match_arms_and_extra_bounds()?;
+    let pad = state.find_flag(&input.attrs, "pad")?;
+    if let Data::Enum(ref data_enum) = input.data {
+        for variant in &data_enum.variants {
+            if state.find_flag(&variant.attrs, "pad")? {
+                return Err(Error::new(
+                    variant.span(),
+                    format!("#[{}(pad)] can only be used on the whole type", trait_attr),
+                ));
+            }
+        }
     }
-    .get_match_arms_and_extra_bounds()?;
 
     let generics = if !bounds.is_empty() {
         let bounds: Vec<_> = bounds
@@ -65,6 +76,107 @@ pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let name = &input.ident;
 
+    // Field values passed to `debug_struct()` and `debug_tuple()` have to implement `Debug`.
+    let debug_impl = if trait_attr == "debug" {
+        quote! {
+            impl <F> ::core::fmt::Debug for _derive_more_DisplayAs<F>
+            where
+                F: Fn(&mut Formatter) -> Result
+            {
+                fn fmt(&self, f: &mut Formatter) -> Result {
+                    (self.0)(f)
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let body = quote! {
+        match self {
+            #arms
+            _ => Ok(()) // This is needed for empty enums
+        }
+    };
+    let body = if pad {
+        // Render into a counting writer first, so that the padding can be computed without
+        // allocating. The actual output is then written between the fill characters.
+        quote! {
+            fn _derive_more_pad(f: &mut Formatter, content: &dyn Display) -> Result {
+                use core::fmt::{Alignment, Write};
+
+                struct _derive_more_Counter(usize);
+
+                impl Write for _derive_more_Counter {
+                    fn write_str(&mut self, s: &str) -> Result {
+                        self.0 += s.chars().count();
+                        Ok(())
+                    }
+                }
+
+                struct _derive_more_Truncate<'a, 'b> {
+                    f: &'a mut Formatter<'b>,
+                    remaining: usize,
+                }
+
+                impl<'a, 'b> Write for _derive_more_Truncate<'a, 'b> {
+                    fn write_str(&mut self, s: &str) -> Result {
+                        match s.char_indices().nth(self.remaining) {
+                            Some((end, _)) => {
+                                self.remaining = 0;
+                                self.f.write_str(&s[..end])
+                            }
+                            None => {
+                                self.remaining -= s.chars().count();
+                                self.f.write_str(s)
+                            }
+                        }
+                    }
+                }
+
+                if f.width().is_none() && f.precision().is_none() {
+                    return write!(f, "{}", content);
+                }
+
+                let mut counter = _derive_more_Counter(0);
+                core::fmt::write(&mut counter, format_args!("{}", content))?;
+                let len = match f.precision() {
+                    Some(precision) if precision < counter.0 => precision,
+                    _ => counter.0,
+                };
+                let padding = f.width().map_or(0, |width| width.saturating_sub(len));
+                let (pre, post) = match f.align() {
+                    Some(Alignment::Right) => (padding, 0),
+                    Some(Alignment::Center) => (padding / 2, padding - padding / 2),
+                    _ => (0, padding),
+                };
+                let fill = f.fill();
+
+                for _ in 0..pre {
+                    f.write_char(fill)?;
+                }
+                match f.precision() {
+                    Some(precision) => core::fmt::write(
+                        &mut _derive_more_Truncate { f, remaining: precision },
+                        format_args!("{}", content),
+                    )?,
+                    None => write!(f, "{}", content)?,
+                }
+                for _ in 0..post {
+                    f.write_char(fill)?;
+                }
+                Ok(())
+            }
+
+            _derive_more_pad(
+                _derive_more_Display_formatter,
+                &_derive_more_DisplayAs(|_derive_more_Display_formatter| #body),
+            )
+        }
+    } else {
+        body
+    };
+
     Ok(quote! {
         impl #impl_generics #trait_path for #name #ty_generics #where_clause
         {
@@ -87,12 +199,11 @@ pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
                             (self.0)(f)
                         }
                     }
+
+                    #debug_impl
                 };
 
-                match self {
-                    #arms
-                    _ => Ok(()) // This is needed for empty enums
-                }
+                #body
             }
         }
     })
@@ -113,31 +224,6 @@ impl<'a, 'b> State<'a, 'b> {
         )
     }
 
-    fn get_matcher(&self, fields: &Fields) -> TokenStream {
-        match fields {
-            Fields::Unit => TokenStream::new(),
-            Fields::Unnamed(fields) => {
-                let fields: TokenStream = (0..fields.unnamed.len())
-                    .map(|n| {
-                        let i = Ident::new(&format!("_{}", n), Span::call_site());
-                        quote!(#i,)
-                    })
-                    .collect();
-                quote!((#fields))
-            }
-            Fields::Named(fields) => {
-                let fields: TokenStream = fields
-                    .named
-                    .iter()
-                    .map(|f| {
-                        let i = f.ident.as_ref().unwrap();
-                        quote!(#i,)
-                    })
-                    .collect();
-                quote!({#fields})
-            }
-        }
-    }
     fn find_meta(&self, attrs: &[Attribute]) -> Result<Option<Meta>> {
         let mut it = attrs
             .iter()
@@ -148,6 +234,10 @@ impl<'a, 'b> State<'a, 'b> {
                 } else {
                     false
                 }
+            })
+            .filter(|m| match self.trait_attr {
+                "debug" => !Self::is_flag_meta(m, "pad") && !Self::is_flag_meta(m, "transparent"),
+                _ => !Self::is_flag_meta(m, "pad"),
             });
 
         let meta = it.next();
@@ -157,6 +247,34 @@ impl<'a, 'b> State<'a, 'b> {
             Ok(meta)
         }
     }
+    fn is_flag_meta(meta: &Meta, flag: &str) -> bool {
+        match meta {
+            Meta::List(list) => {
+                list.nested.len() == 1
+                    && match &list.nested[0] {
+                        NestedMeta::Meta(Meta::Path(path)) => path.is_ident(flag),
+                        _ => false,
+                    }
+            }
+            _ => false,
+        }
+    }
+    fn find_flag(&self, attrs: &[Attribute], flag: &str) -> Result<bool> {
+        let mut it = attrs
+            .iter()
+            .filter_map(|m| m.parse_meta().ok())
+            .filter(|m| m.path().is_ident(self.trait_attr) && Self::is_flag_meta(m, flag));
+
+        let meta = it.next();
+        if it.next().is_some() {
+            Err(Error::new(
+                meta.span(),
+                format!("`{}` given more than once", flag),
+            ))
+        } else {
+            Ok(meta.is_some())
+        }
+    }
     fn get_meta_fmt(&self, meta: &Meta, outer_enum: bool) -> Result<(TokenStream, bool)> {
         let list = match meta {
             Meta::List(list) => list,
@@ -296,9 +414,14 @@ impl<'a, 'b> State<'a, 'b> {
                     }
                     Some((outer_fmt, true)) => {
                         let fmt: Result<TokenStream> = e.variants.iter().try_fold(TokenStream::new(), |arms, v| {
-                            let matcher = self.get_matcher(&v.fields);
+                            let matcher = get_matcher(&v.fields);
                             let fmt = if let Some(meta) = self.find_meta(&v.attrs)? {
                                 self.get_meta_fmt(&meta, false)?.0
+                            } else if self.is_debug_struct(&v.attrs)? {
+                                let formatter =
+                                    Ident::new("_derive_more_Debug_formatter", Span::call_site());
+                                let fmt = self.get_debug_struct_fmt(&v.fields, &v.ident, &formatter)?;
+                                quote!(_derive_more_DisplayAs(|#formatter| #fmt))
                             } else {
                                 self.infer_fmt(&v.fields, &v.ident)?
                             };
@@ -313,17 +436,23 @@ impl<'a, 'b> State<'a, 'b> {
                         ))
                     }
                     None => e.variants.iter().try_fold((TokenStream::new(), HashMap::new()), |(arms, mut all_bounds), v| {
-                        let matcher = self.get_matcher(&v.fields);
+                        let matcher = get_matcher(&v.fields);
                         let name = &self.input.ident;
                         let v_name = &v.ident;
-                        let fmt: TokenStream;
+                        let formatter = Ident::new("_derive_more_Display_formatter", Span::call_site());
+                        let body: TokenStream;
                         let bounds: HashMap<_, _>;
 
                         if let Some(meta) = self.find_meta(&v.attrs)? {
-                            fmt = self.get_meta_fmt(&meta, false)?.0;
+                            let fmt = self.get_meta_fmt(&meta, false)?.0;
+                            body = quote!(write!(#formatter, "{}", #fmt));
                             bounds = self.get_used_type_params_bounds(&v.fields, &meta);
+                        } else if self.is_debug_struct(&v.attrs)? {
+                            body = self.get_debug_struct_fmt(&v.fields, v_name, &formatter)?;
+                            bounds = self.infer_debug_struct_bounds(&v.fields)?;
                         } else {
-                            fmt = self.infer_fmt(&v.fields, v_name)?;
+                            let fmt = self.infer_fmt(&v.fields, v_name)?;
+                            body = quote!(write!(#formatter, "{}", #fmt));
                             bounds = self.infer_type_params_bounds(&v.fields);
                         };
                         all_bounds = bounds.into_iter()
@@ -333,28 +462,34 @@ impl<'a, 'b> State<'a, 'b> {
                             });
 
                         Ok((
-                            quote_spanned!(self.input.span()=> #arms #name::#v_name #matcher => write!(_derive_more_Display_formatter, "{}", #fmt),),
+                            quote_spanned!(self.input.span()=> #arms #name::#v_name #matcher => #body,),
                             all_bounds,
                         ))
                     }),
                 }
             }
             Data::Struct(s) => {
-                let matcher = self.get_matcher(&s.fields);
+                let matcher = get_matcher(&s.fields);
                 let name = &self.input.ident;
-                let fmt: TokenStream;
+                let formatter = Ident::new("_derive_more_Display_formatter", Span::call_site());
+                let body: TokenStream;
                 let bounds: HashMap<_, _>;
 
                 if let Some(meta) = self.find_meta(&self.input.attrs)? {
-                    fmt = self.get_meta_fmt(&meta, false)?.0;
+                    let fmt = self.get_meta_fmt(&meta, false)?.0;
+                    body = quote!(write!(#formatter, "{}", #fmt));
                     bounds = self.get_used_type_params_bounds(&s.fields, &meta);
+                } else if self.is_debug_struct(&self.input.attrs)? {
+                    body = self.get_debug_struct_fmt(&s.fields, name, &formatter)?;
+                    bounds = self.infer_debug_struct_bounds(&s.fields)?;
                 } else {
-                    fmt = self.infer_fmt(&s.fields, name)?;
+                    let fmt = self.infer_fmt(&s.fields, name)?;
+                    body = quote!(write!(#formatter, "{}", #fmt));
                     bounds = self.infer_type_params_bounds(&s.fields);
                 }
 
                 Ok((
-                    quote_spanned!(self.input.span()=> #name #matcher => write!(_derive_more_Display_formatter, "{}", #fmt),),
+                    quote_spanned!(self.input.span()=> #name #matcher => #body,),
                     bounds,
                 ))
             }
@@ -483,6 +618,7 @@ impl<'a, 'b> State<'a, 'b> {
                         "lower_exp" => "LowerExp",
                         "upper_exp" => "UpperExp",
                         "pointer" => "Pointer",
+                        "debug" => "Debug",
                         _ => unreachable!(),
                     }]
                     .iter()
@@ -492,6 +628,82 @@ impl<'a, 'b> State<'a, 'b> {
             })
             .collect()
     }
+    /// Whether `Debug` output should be generated the way std's derive does it, instead of
+    /// forwarding to the single field of a struct or variant marked with `#[debug(transparent)]`.
+    fn is_debug_struct(&self, attrs: &[Attribute]) -> Result<bool> {
+        Ok(self.trait_attr == "debug"
+            && !self.find_flag(&self.input.attrs, "transparent")?
+            && !self.find_flag(attrs, "transparent")?)
+    }
+    fn get_debug_struct_fmt(
+        &self,
+        fields: &Fields,
+        name: &Ident,
+        formatter: &Ident,
+    ) -> Result<TokenStream> {
+        let name = unraw(name);
+        match fields {
+            Fields::Unit => Ok(quote!(#formatter.write_str(#name))),
+            Fields::Named(named) => {
+                let fields: Result<TokenStream> =
+                    named
+                        .named
+                        .iter()
+                        .try_fold(TokenStream::new(), |fields, f| {
+                            let ident = f.ident.as_ref().unwrap();
+                            Ok(match DebugField::parse(f)?.value(&quote!(#ident)) {
+                                Some(value) => {
+                                    let field_name = unraw(ident);
+                                    quote!(#fields .field(#field_name, #value))
+                                }
+                                None => fields,
+                            })
+                        });
+                let fields = fields?;
+                Ok(quote!(#formatter.debug_struct(#name) #fields .finish()))
+            }
+            Fields::Unnamed(unnamed) => {
+                let fields: Result<TokenStream> = unnamed.unnamed.iter().enumerate().try_fold(
+                    TokenStream::new(),
+                    |fields, (n, f)| {
+                        let i = Ident::new(&format!("_{}", n), Span::call_site());
+                        Ok(match DebugField::parse(f)?.value(&quote!(#i)) {
+                            Some(value) => quote!(#fields .field(#value)),
+                            None => fields,
+                        })
+                    },
+                );
+                let fields = fields?;
+                Ok(quote!(#formatter.debug_tuple(#name) #fields .finish()))
+            }
+        }
+    }
+    fn infer_debug_struct_bounds(
+        &self,
+        fields: &Fields,
+    ) -> Result<HashMap<Type, HashSet<&'static str>>> {
+        let mut bounds = HashMap::new();
+        if self.type_params.is_empty() {
+            return Ok(bounds);
+        }
+        for field in fields {
+            let trait_names: Vec<_> = match DebugField::parse(field)? {
+                DebugField::Default => vec!["Debug"],
+                DebugField::Fmt(fmt) => Placeholder::parse_fmt_string(&fmt.value())
+                    .into_iter()
+                    .map(|pl| pl.trait_name)
+                    .collect(),
+                DebugField::Skip | DebugField::Redact | DebugField::With(_) => vec![],
+            };
+            if !trait_names.is_empty() && self.has_type_param_in(field) {
+                bounds
+                    .entry(field.ty.clone())
+                    .or_insert_with(HashSet::new)
+                    .extend(trait_names);
+            }
+        }
+        Ok(bounds)
+    }
     fn has_type_param_in(&self, field: &syn::Field) -> bool {
         if let Type::Path(ref ty) = field.ty {
             return match ty.path.segments.first() {
@@ -503,6 +715,68 @@ impl<'a, 'b> State<'a, 'b> {
     }
 }
 
+/// How a single field is rendered by an inferred `Debug` implementation.
+enum DebugField {
+    Default,
+    Skip,
+    Redact,
+    Fmt(LitStr),
+    With(Path),
+}
+
+impl DebugField {
+    fn parse(field: &syn::Field) -> Result<Self> {
+        let proper_syntax = r#"Proper syntax: #[debug(skip)], #[debug(redact)], #[debug(fmt = "{:#x}")] or #[debug(with = "my_fmt_fn")]"#;
+        let mut it = field.attrs.iter().filter(|a| a.path.is_ident("debug"));
+        let attr = match it.next() {
+            Some(attr) => attr,
+            None => return Ok(DebugField::Default),
+        };
+        if let Some(attr) = it.next() {
+            return Err(Error::new(attr.span(), "Too many debug attributes given"));
+        }
+
+        let meta = attr.parse_meta()?;
+        let nested = match &meta {
+            Meta::List(list) if list.nested.len() == 1 => &list.nested[0],
+            _ => return Err(Error::new(meta.span(), proper_syntax)),
+        };
+        match nested {
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => Ok(DebugField::Skip),
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("redact") => Ok(DebugField::Redact),
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(fmt),
+                ..
+            })) if path.is_ident("fmt") => Ok(DebugField::Fmt(fmt.clone())),
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(with),
+                ..
+            })) if path.is_ident("with") => Ok(DebugField::With(with.parse()?)),
+            _ => Err(Error::new(nested.span(), proper_syntax)),
+        }
+    }
+
+    /// Returns the value to pass to `.field()` for a field bound to `var`, or `None` when the
+    /// field is skipped.
+    fn value(&self, var: &TokenStream) -> Option<TokenStream> {
+        match self {
+            DebugField::Default => Some(quote!(#var)),
+            DebugField::Skip => None,
+            DebugField::Redact => Some(quote!(&_derive_more_DisplayAs(
+                |_derive_more_Debug_formatter| _derive_more_Debug_formatter.write_str("<redacted>")
+            ))),
+            DebugField::Fmt(fmt) => Some(quote!(&_derive_more_DisplayAs(
+                |_derive_more_Debug_formatter| write!(_derive_more_Debug_formatter, #fmt, #var)
+            ))),
+            DebugField::With(with) => Some(quote!(&_derive_more_DisplayAs(
+                |_derive_more_Debug_formatter| #with(#var, _derive_more_Debug_formatter)
+            ))),
+        }
+    }
+}
+
 /// Representation of formatting placeholder.
 #[derive(Debug, PartialEq)]
 struct Placeholder {
@@ -0,0 +1,205 @@
+use std::ptr;
+
+use crate::utils::{add_extra_where_clauses, field_member, get_attr_args, is_flag};
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{Error, Result},
+    spanned::Spanned,
+    Attribute, Data, DeriveInput, Field, Fields, Index, Member,
+};
+
+/// Provides the hook to expand `#[derive(Error)]` into an implementation of `Error`
+pub fn expand(input: &DeriveInput, _: &str) -> Result<TokenStream> {
+    let input_type = &input.ident;
+    let transparent = is_transparent(&input.attrs)?;
+
+    let mut sources = vec![];
+    match input.data {
+        Data::Struct(ref data_struct) => {
+            let source = Source::parse(&data_struct.fields, transparent, input.span())?;
+            sources.push((quote!(#input_type), source));
+        }
+        Data::Enum(ref data_enum) => {
+            for variant in &data_enum.variants {
+                let transparent = transparent || is_transparent(&variant.attrs)?;
+                let source = Source::parse(&variant.fields, transparent, variant.span())?;
+                let variant_ident = &variant.ident;
+                sources.push((quote!(#input_type::#variant_ident), source));
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new(
+                input.span(),
+                "Only structs and enums can derive(Error)",
+            ))
+        }
+    }
+
+    let mut tokens = TokenStream::new();
+    if transparent {
+        crate::display::expand(input, "Display")?.to_tokens(&mut tokens);
+    }
+    for (path, source) in &sources {
+        if let Some(field) = source.from {
+            from_impl(input, path, field).to_tokens(&mut tokens);
+        }
+    }
+
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let mut where_clauses = vec![];
+    if input.generics.type_params().next().is_some() {
+        where_clauses.push(quote! {
+            #input_type#ty_generics: ::core::fmt::Debug + ::core::fmt::Display
+        });
+        for (_, source) in &sources {
+            if let Some(field) = source.field {
+                let field_type = &field.ty;
+                where_clauses.push(quote!(#field_type: ::std::error::Error + 'static));
+            }
+        }
+    }
+    let generics = if where_clauses.is_empty() {
+        input.generics.clone()
+    } else {
+        add_extra_where_clauses(&input.generics, quote!(where #(#where_clauses),*))
+    };
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let source_fn = if sources.iter().any(|(_, source)| source.field.is_some()) {
+        let arms = sources.iter().map(|(path, source)| match source.field {
+            Some(_) => {
+                let member = &source.member;
+                let body = if source.transparent {
+                    quote!(::std::error::Error::source(source))
+                } else {
+                    quote!(::core::option::Option::Some(
+                        source as &(dyn ::std::error::Error + 'static)
+                    ))
+                };
+                quote!(#path { #member: source, .. } => #body)
+            }
+            None => quote!(#path { .. } => ::core::option::Option::None),
+        });
+        quote! {
+            fn source(&self) -> ::core::option::Option<&(dyn ::std::error::Error + 'static)> {
+                match self {
+                    #(#arms),*
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    quote! {
+        impl#impl_generics ::std::error::Error for #input_type#ty_generics #where_clause {
+            #source_fn
+        }
+    }
+    .to_tokens(&mut tokens);
+    Ok(tokens)
+}
+
+/// The error source of a struct or of a single enum variant.
+struct Source<'a> {
+    field: Option<&'a Field>,
+    member: Member,
+    transparent: bool,
+    from: Option<&'a Field>,
+}
+
+impl<'a> Source<'a> {
+    fn parse(fields: &'a Fields, transparent: bool, span: Span) -> Result<Self> {
+        let field_count = fields.iter().count();
+        if transparent && field_count != 1 {
+            return Err(Error::new(
+                span,
+                "#[error(transparent)] requires exactly one field",
+            ));
+        }
+
+        let mut explicit: Option<&Field> = None;
+        let mut from = None;
+        for field in fields {
+            for arg in get_attr_args(&field.attrs, "error")?.unwrap_or_default() {
+                if is_flag(&arg, "from") {
+                    if field_count != 1 {
+                        return Err(Error::new(
+                            arg.span(),
+                            "#[error(from)] can only be used when there is a single field",
+                        ));
+                    }
+                    from = Some(field);
+                } else if !is_flag(&arg, "source") {
+                    return Err(Error::new(
+                        arg.span(),
+                        "Proper syntax: #[error(source)] or #[error(from)]",
+                    ));
+                }
+                match explicit {
+                    Some(other) if !ptr::eq(other, field) => {
+                        return Err(Error::new(field.span(), "Multiple error sources given"))
+                    }
+                    _ => explicit = Some(field),
+                }
+            }
+        }
+
+        let field = if transparent {
+            fields.iter().next()
+        } else {
+            explicit.or_else(|| {
+                fields.iter().find(|f| match f.ident {
+                    Some(ref ident) => ident == "source",
+                    None => false,
+                })
+            })
+        };
+        let member = match field {
+            Some(field) => {
+                let i = fields.iter().position(|f| ptr::eq(f, field)).unwrap();
+                field_member(i, field)
+            }
+            None => Member::Unnamed(Index::from(0)),
+        };
+        Ok(Source {
+            field,
+            member,
+            transparent,
+            from,
+        })
+    }
+}
+
+fn is_transparent(attrs: &[Attribute]) -> Result<bool> {
+    let args = match get_attr_args(attrs, "error")? {
+        Some(args) => args,
+        None => return Ok(false),
+    };
+    for arg in &args {
+        if !is_flag(arg, "transparent") {
+            return Err(Error::new(
+                arg.span(),
+                "Proper syntax: #[error(transparent)]",
+            ));
+        }
+    }
+    Ok(true)
+}
+
+fn from_impl<T: ToTokens>(input: &DeriveInput, path: &T, field: &Field) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let input_type = &input.ident;
+    let field_type = &field.ty;
+    // `#[error(from)]` is only allowed when there's a single field
+    let member = field_member(0, field);
+    quote! {
+        impl#impl_generics ::core::convert::From<#field_type> for #input_type#ty_generics #where_clause {
+            #[inline]
+            fn from(source: #field_type) -> #input_type#ty_generics {
+                #path { #member: source }
+            }
+        }
+    }
+}
@@ -1,13 +1,40 @@
 use std::collections::HashMap;
 use std::ops::Index;
 
-use crate::utils::{field_idents, get_field_types, named_to_vec, number_idents, unnamed_to_vec};
+use crate::utils::{
+    field_idents, get_attr_args, get_common_field_type, get_field_types, is_flag, named_to_vec,
+    number_idents, numbered_vars, unnamed_to_vec,
+};
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
-use syn::{Data, DataEnum, DeriveInput, Field, Fields};
+use syn::{
+    parse::{Error, Result},
+    spanned::Spanned,
+    Data, DataEnum, DeriveInput, Field, Fields,
+};
 
 /// Provides the hook to expand `#[derive(From)]` into an implementation of `From`
-pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
+    if let Some(args) = get_attr_args(&input.attrs, "from")? {
+        match (args.as_slice(), &input.data) {
+            ([arg], Data::Struct(ref data_struct)) if is_flag(arg, "array") => {
+                let mut tokens = expand_tuple(input, trait_name);
+                array_from(input, &data_struct.fields)?.to_tokens(&mut tokens);
+                return Ok(tokens);
+            }
+            ([arg], _) if is_flag(arg, "array") => {
+                return Err(Error::new(
+                    arg.span(),
+                    "#[from(array)] can only be used on structs",
+                ))
+            }
+            _ => return Err(Error::new(input.span(), "Proper syntax: #[from(array)]")),
+        }
+    }
+    Ok(expand_tuple(input, trait_name))
+}
+
+fn expand_tuple(input: &DeriveInput, trait_name: &str) -> TokenStream {
     match input.data {
         Data::Struct(ref data_struct) => match data_struct.fields {
             Fields::Unnamed(ref fields) => tuple_from(input, &unnamed_to_vec(fields)),
@@ -39,6 +66,33 @@ pub fn from_impl<T: ToTokens>(input: &DeriveInput, fields: &[&Field], body: T) -
     }
 }
 
+/// Implements `From<[T; N]>` for structs whose fields all have the same type
+fn array_from(input: &DeriveInput, fields: &Fields) -> Result<TokenStream> {
+    let field_type = get_common_field_type(fields, input.span(), "#[from(array)]")?;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let input_type = &input.ident;
+    let len = fields.iter().count();
+    let vars = &numbered_vars(len, "");
+    let body = match fields {
+        Fields::Named(_) => {
+            let field_names = fields.iter().map(|f| &f.ident);
+            quote!(#input_type{#(#field_names: #vars),*})
+        }
+        _ => quote!(#input_type(#(#vars),*)),
+    };
+    Ok(quote! {
+        impl#impl_generics ::core::convert::From<[#field_type; #len]> for
+            #input_type#ty_generics #where_clause {
+
+            #[inline]
+            fn from(original: [#field_type; #len]) -> #input_type#ty_generics {
+                let [#(#vars),*] = original;
+                #body
+            }
+        }
+    })
+}
+
 fn tuple_from(input: &DeriveInput, fields: &[&Field]) -> TokenStream {
     let input_type = &input.ident;
     let body = tuple_body(input_type, fields);
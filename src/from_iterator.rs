@@ -0,0 +1,197 @@
+use crate::utils::{
+    add_extra_generic_param, add_extra_where_clauses, field_member, get_attr_args,
+    get_forwarded_field, get_types_arg,
+};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse::{Error, Result},
+    parse_quote,
+    spanned::Spanned,
+    Data, DeriveInput, Fields, GenericArgument, Generics, Ident, Member, PathArguments, Type,
+};
+
+/// Provides the hook to expand `#[derive(FromIterator)]` and `#[derive(Extend)]` into an
+/// implementation of `FromIterator` or `Extend` respectively, which forwards to the collection
+/// field of a struct
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
+    let fields = match input.data {
+        Data::Struct(ref data_struct) => &data_struct.fields,
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                format!("Only structs can derive({})", trait_name),
+            ))
+        }
+    };
+    let (member, field, field_args) =
+        get_forwarded_field(fields, input.span(), trait_name, "from_iterator")?;
+    let container_args = get_attr_args(&input.attrs, "from_iterator")?.unwrap_or_default();
+    let mut types: Option<Vec<Type>> = None;
+    for arg in container_args.iter().chain(&field_args) {
+        match get_types_arg(arg)? {
+            Some(arg_types) => types.get_or_insert_with(Vec::new).extend(arg_types),
+            None => {
+                return Err(Error::new(
+                    arg.span(),
+                    "Proper syntax: #[from_iterator] or #[from_iterator(types(...))]",
+                ))
+            }
+        }
+    }
+
+    let trait_ident = Ident::new(trait_name, Span::call_site());
+    let trait_path = &quote!(::core::iter::#trait_ident);
+    let field_type = &field.ty;
+    Ok(match types {
+        // A generic implementation would overlap with the ones for the given types, so in that
+        // case only the given types are supported, which can include the item type of the field
+        // itself, because every type can be converted into itself.
+        Some(types) => types
+            .into_iter()
+            .map(|mut item_type| {
+                let lifetime = quote!('__deriveMoreLifetime);
+                let generics = if name_elided_lifetimes(&mut item_type, &lifetime) {
+                    add_extra_generic_param(&input.generics, lifetime)
+                } else {
+                    input.generics.clone()
+                };
+                let field_trait = quote!(<#field_type as #trait_path<_>>);
+                let items = quote! {
+                    ::core::iter::IntoIterator::into_iter(iter).map(::core::convert::Into::into)
+                };
+                let body = method_body(input, fields, &member, trait_name, &field_trait, &items);
+                impl_trait(input, &generics, trait_name, &item_type, &body)
+            })
+            .collect(),
+        None => {
+            let item_type: Type = parse_quote!(__ItemT);
+            let generics = add_extra_generic_param(
+                &add_extra_where_clauses(
+                    &input.generics,
+                    quote!(where #field_type: #trait_path<#item_type>),
+                ),
+                quote!(#item_type),
+            );
+            let field_trait = quote!(<#field_type as #trait_path<#item_type>>);
+            let body = method_body(
+                input,
+                fields,
+                &member,
+                trait_name,
+                &field_trait,
+                &quote!(iter),
+            );
+            impl_trait(input, &generics, trait_name, &item_type, &body)
+        }
+    })
+}
+
+/// Implements the trait for `item_type`, with `body` as the implementation of its method
+fn impl_trait(
+    input: &DeriveInput,
+    generics: &Generics,
+    trait_name: &str,
+    item_type: &Type,
+    body: &TokenStream,
+) -> TokenStream {
+    let input_type = &input.ident;
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let method = if trait_name == "Extend" {
+        quote! {
+            #[inline]
+            fn extend<__IterT>(&mut self, iter: __IterT)
+            where
+                __IterT: ::core::iter::IntoIterator<Item = #item_type>,
+            {
+                #body
+            }
+        }
+    } else {
+        quote! {
+            #[inline]
+            fn from_iter<__IterT>(iter: __IterT) -> Self
+            where
+                __IterT: ::core::iter::IntoIterator<Item = #item_type>,
+            {
+                #body
+            }
+        }
+    };
+    let trait_ident = Ident::new(trait_name, Span::call_site());
+    quote! {
+        impl#impl_generics ::core::iter::#trait_ident<#item_type> for #input_type#ty_generics
+            #where_clause
+        {
+            #method
+        }
+    }
+}
+
+/// Returns the body of the method, which calls the method of `field_trait` on the forwarded field
+/// with `items`. For `FromIterator` the other fields are created with `Default`.
+fn method_body(
+    input: &DeriveInput,
+    fields: &Fields,
+    member: &Member,
+    trait_name: &str,
+    field_trait: &TokenStream,
+    items: &TokenStream,
+) -> TokenStream {
+    if trait_name == "Extend" {
+        return quote!(#field_trait::extend(&mut self.#member, #items));
+    }
+    let input_type = &input.ident;
+    let initializers = fields.iter().enumerate().map(|(i, field)| {
+        let field_member = field_member(i, field);
+        if field_member == *member {
+            quote!(#field_member: #field_trait::from_iter(#items))
+        } else {
+            quote!(#field_member: ::core::default::Default::default())
+        }
+    });
+    quote! {
+        #input_type {
+            #(#initializers),*
+        }
+    }
+}
+
+/// Gives the references in `ty` without a lifetime, like `&str`, the given lifetime, because they
+/// can't be elided in the bounds of the generated methods. Returns whether there were any.
+fn name_elided_lifetimes(ty: &mut Type, lifetime: &TokenStream) -> bool {
+    match ty {
+        Type::Reference(reference) => {
+            let elided = reference.lifetime.is_none();
+            if elided {
+                reference.lifetime = Some(parse_quote!(#lifetime));
+            }
+            name_elided_lifetimes(&mut reference.elem, lifetime) || elided
+        }
+        Type::Slice(slice) => name_elided_lifetimes(&mut slice.elem, lifetime),
+        Type::Array(array) => name_elided_lifetimes(&mut array.elem, lifetime),
+        Type::Paren(paren) => name_elided_lifetimes(&mut paren.elem, lifetime),
+        Type::Tuple(tuple) => {
+            let mut named = false;
+            for elem in &mut tuple.elems {
+                named |= name_elided_lifetimes(elem, lifetime);
+            }
+            named
+        }
+        Type::Path(path) => {
+            let mut named = false;
+            for segment in &mut path.path.segments {
+                if let PathArguments::AngleBracketed(ref mut args) = segment.arguments {
+                    for arg in &mut args.args {
+                        if let GenericArgument::Type(ty) = arg {
+                            named |= name_elided_lifetimes(ty, lifetime);
+                        }
+                    }
+                }
+            }
+            named
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,198 @@
+use crate::utils::{get_attr_args, get_str_arg, is_flag};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Error, Result},
+    spanned::Spanned,
+    Data, DeriveInput, Fields, Ident, NestedMeta, Type, Visibility,
+};
+
+/// How a getter returns the field
+#[derive(Clone, Copy)]
+enum Mode {
+    Copy,
+    Clone,
+    Ref,
+    Deref,
+}
+
+/// The arguments of a `#[getter(...)]` attribute
+#[derive(Default)]
+struct GetterArgs {
+    mode: Option<Mode>,
+    vis: Option<Visibility>,
+    mutable: bool,
+    skip: bool,
+}
+
+impl GetterArgs {
+    fn parse(args: &[NestedMeta], allow_skip: bool) -> Result<Self> {
+        let mut getter_args = GetterArgs::default();
+        for arg in args {
+            let mode = [
+                ("copy", Mode::Copy),
+                ("clone", Mode::Clone),
+                ("ref", Mode::Ref),
+                ("deref", Mode::Deref),
+            ]
+            .iter()
+            .find(|(flag, _)| is_flag(arg, flag));
+            if let Some((_, mode)) = mode {
+                if getter_args.mode.is_some() {
+                    return Err(Error::new(
+                        arg.span(),
+                        "Only one of copy, clone, ref and deref can be given",
+                    ));
+                }
+                getter_args.mode = Some(*mode);
+            } else if is_flag(arg, "mut") {
+                getter_args.mutable = true;
+            } else if allow_skip && is_flag(arg, "skip") {
+                getter_args.skip = true;
+            } else if let Some(vis) = get_str_arg(arg, "vis")? {
+                getter_args.vis = Some(vis.parse()?);
+            } else {
+                return Err(Error::new(
+                    arg.span(),
+                    r#"Proper syntax: #[getter(copy|clone|ref|deref, mut, vis = "pub(crate)")] or #[getter(skip)]"#,
+                ));
+            }
+        }
+        if getter_args.skip && args.len() > 1 {
+            return Err(Error::new(
+                args[0].span(),
+                "#[getter(skip)] cannot be combined with other arguments",
+            ));
+        }
+        Ok(getter_args)
+    }
+}
+
+/// Provides the hook to expand `#[derive(Getters)]` into a getter method for every field of a
+/// struct, and optionally a `*_mut` method that returns a mutable reference to it
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
+    let fields = match input.data {
+        Data::Struct(ref data_struct) => match data_struct.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    input.span(),
+                    format!("Only structs with named fields can derive({})", trait_name),
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                format!("Only structs can derive({})", trait_name),
+            ))
+        }
+    };
+    let container_args = GetterArgs::parse(
+        &get_attr_args(&input.attrs, "getter")?.unwrap_or_default(),
+        false,
+    )?;
+
+    let mut methods = vec![];
+    for field in fields {
+        let args = GetterArgs::parse(
+            &get_attr_args(&field.attrs, "getter")?.unwrap_or_default(),
+            true,
+        )?;
+        if args.skip {
+            continue;
+        }
+        let field_ident = field.ident.as_ref().unwrap();
+        let field_name = field_ident.to_string();
+        let field_name = field_name.trim_start_matches("r#");
+        let ty = &field.ty;
+        let vis = match args.vis.as_ref().or(container_args.vis.as_ref()) {
+            Some(vis) => quote!(#vis),
+            None => quote!(pub),
+        };
+        // Only the docs of the field are copied, because other attributes like `#[serde]`
+        // wouldn't make sense on a method.
+        let docs: Vec<_> = field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("doc"))
+            .collect();
+        let doc_or = |fallback: String| {
+            if docs.is_empty() {
+                quote!(#[doc = #fallback])
+            } else {
+                quote!(#(#docs)*)
+            }
+        };
+
+        let mode = args.mode.or(container_args.mode).unwrap_or_else(|| {
+            if is_copy(ty) {
+                Mode::Copy
+            } else {
+                Mode::Ref
+            }
+        });
+        let (output, body) = match mode {
+            Mode::Copy => (quote!(#ty), quote!(self.#field_ident)),
+            Mode::Clone => (
+                quote!(#ty),
+                quote!(::core::clone::Clone::clone(&self.#field_ident)),
+            ),
+            Mode::Ref => (quote!(&#ty), quote!(&self.#field_ident)),
+            Mode::Deref => (
+                quote!(&<#ty as ::core::ops::Deref>::Target),
+                quote!(::core::ops::Deref::deref(&self.#field_ident)),
+            ),
+        };
+        let doc = doc_or(format!("Returns the `{}` field.", field_name));
+        methods.push(quote! {
+            #doc
+            #[inline]
+            #vis fn #field_ident(&self) -> #output {
+                #body
+            }
+        });
+
+        if args.mutable || container_args.mutable {
+            let method_ident = Ident::new(&format!("{}_mut", field_name), field_ident.span());
+            let doc = doc_or(format!(
+                "Returns a mutable reference to the `{}` field.",
+                field_name
+            ));
+            methods.push(quote! {
+                #doc
+                #[inline]
+                #vis fn #method_ident(&mut self) -> &mut #ty {
+                    &mut self.#field_ident
+                }
+            });
+        }
+    }
+
+    let input_type = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl#impl_generics #input_type#ty_generics #where_clause {
+            #(#methods)*
+        }
+    })
+}
+
+/// Returns whether `ty` is known to be `Copy`, so its getter returns it by value. These are the
+/// primitive types, shared references, and tuples and arrays of them.
+fn is_copy(ty: &Type) -> bool {
+    const PRIMITIVES: &[&str] = &[
+        "bool", "char", "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16",
+        "u32", "u64", "u128", "usize",
+    ];
+    match ty {
+        Type::Path(path) => {
+            path.qself.is_none() && PRIMITIVES.iter().any(|name| path.path.is_ident(name))
+        }
+        Type::Reference(reference) => reference.mutability.is_none(),
+        Type::Tuple(tuple) => tuple.elems.iter().all(is_copy),
+        Type::Array(array) => is_copy(&array.elem),
+        Type::Paren(paren) => is_copy(&paren.elem),
+        _ => false,
+    }
+}
@@ -1,76 +1,177 @@
-use crate::utils::{add_where_clauses_for_new_ident, named_to_vec, unnamed_to_vec};
+use crate::utils::{
+    add_extra_generic_param, add_extra_where_clauses, field_member, get_attr_args,
+    get_common_field_type, get_forwarded_field, get_variant_forwarded_fields, is_flag,
+    variant_field_match,
+};
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{Data, DeriveInput, Field, Fields, Ident};
+use syn::{
+    parse::{Error, Result},
+    spanned::Spanned,
+    Data, DeriveInput, Fields, Ident, NestedMeta,
+};
 
-/// Provides the hook to expand `#[derive(Index)]` into an implementation of `From`
-pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
+/// Provides the hook to expand `#[derive(Index)]` and `#[derive(IndexMut)]` into an
+/// implementation of `Index` or `IndexMut` respectively
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
     let trait_ident = Ident::new(trait_name, Span::call_site());
     let index_type = &Ident::new("__IdxT", Span::call_site());
     let trait_path = &quote!(::core::ops::#trait_ident<#index_type>);
     let input_type = &input.ident;
-    let field_vec: Vec<&Field>;
-    let member = match input.data {
-        Data::Struct(ref data_struct) => match data_struct.fields {
-            Fields::Unnamed(ref fields) => {
-                field_vec = unnamed_to_vec(fields);
-                tuple_from_str(trait_name, &field_vec)
+    let (field_type, field_ref, field_mut) = match input.data {
+        Data::Struct(ref data_struct) => {
+            let fields = &data_struct.fields;
+            if let Some(args) = get_attr_args(&input.attrs, "index")? {
+                return match args.as_slice() {
+                    [arg] if is_flag(arg, "fields") => expand_positional(input, fields, trait_name),
+                    _ => Err(Error::new(input.span(), "Proper syntax: #[index(fields)]")),
+                };
             }
-            Fields::Named(ref fields) => {
-                field_vec = named_to_vec(fields);
-                struct_from_str(trait_name, &field_vec)
+            let (member, field, args) =
+                get_forwarded_field(fields, input.span(), trait_name, "index")?;
+            check_field_args(&args)?;
+            (&field.ty, quote!(&self.#member), quote!(&mut self.#member))
+        }
+        Data::Enum(ref data_enum) => {
+            let (field_type, forwarded) =
+                get_variant_forwarded_fields(data_enum, input.span(), trait_name, "index")?;
+            for (_, _, args) in &forwarded {
+                check_field_args(args)?;
             }
-            Fields::Unit => panic_one_field(trait_name),
-        },
-        _ => panic_one_field(trait_name),
+            let field_match = variant_field_match(input_type, &forwarded);
+            (field_type, field_match.clone(), field_match)
+        }
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                format!("Only structs and enums can derive({})", trait_name),
+            ))
+        }
     };
-    let field_type = &field_vec[0].ty;
     let type_where_clauses = quote! {
         where #field_type: #trait_path
     };
 
-    let new_generics = add_where_clauses_for_new_ident(
-        &input.generics,
-        &field_vec,
-        index_type,
-        type_where_clauses,
+    let new_generics = add_extra_generic_param(
+        &add_extra_where_clauses(&input.generics, type_where_clauses),
+        quote!(#index_type),
     );
 
     let (impl_generics, _, where_clause) = new_generics.split_for_impl();
     let (_, ty_generics, _) = input.generics.split_for_impl();
-    // let generics = add_extra_ty_param_bound(&input.generics, trait_path);
     let casted_trait = &quote!(<#field_type as #trait_path>);
-    quote! {
-        impl#impl_generics #trait_path for #input_type#ty_generics #where_clause
-        {
+    let body = if trait_name == "IndexMut" {
+        quote! {
+            #[inline]
+            fn index_mut(&mut self, idx: #index_type) -> &mut Self::Output {
+                #casted_trait::index_mut(#field_mut, idx)
+            }
+        }
+    } else {
+        quote! {
             type Output = #casted_trait::Output;
             #[inline]
             fn index(&self, idx: #index_type) -> &Self::Output {
-                #casted_trait::index(&#member, idx)
+                #casted_trait::index(#field_ref, idx)
             }
         }
-    }
+    };
+    Ok(quote! {
+        impl#impl_generics #trait_path for #input_type#ty_generics #where_clause
+        {
+            #body
+        }
+    })
 }
 
-fn panic_one_field(trait_name: &str) -> ! {
-    panic!(format!(
-        "Only structs with one field can derive({})",
-        trait_name
-    ))
+/// Checks that a field is only marked with a plain `#[index]`.
+fn check_field_args(args: &[NestedMeta]) -> Result<()> {
+    match args.first() {
+        Some(arg) => Err(Error::new(arg.span(), "Proper syntax: #[index]")),
+        None => Ok(()),
+    }
 }
 
-fn tuple_from_str<'a>(trait_name: &str, fields: &[&'a Field]) -> (TokenStream) {
-    if fields.len() != 1 {
-        panic_one_field(trait_name)
-    };
-    quote!(self.0)
-}
+/// Implements `Index<usize>` or `IndexMut<usize>` by mapping every index onto the field at that
+/// position, for structs marked with `#[index(fields)]`. Also adds an `iter` or `iter_mut` method
+/// respectively, to iterate over the fields.
+fn expand_positional(
+    input: &DeriveInput,
+    fields: &Fields,
+    trait_name: &str,
+) -> Result<TokenStream> {
+    let field_type = get_common_field_type(fields, input.span(), "#[index(fields)]")?;
+    for field in fields {
+        if get_attr_args(&field.attrs, "index")?.is_some() {
+            return Err(Error::new(
+                field.span(),
+                "#[index] cannot be used on fields together with #[index(fields)]",
+            ));
+        }
+    }
 
-fn struct_from_str<'a>(trait_name: &str, fields: &[&'a Field]) -> TokenStream {
-    if fields.len() != 1 {
-        panic_one_field(trait_name)
+    let input_type = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let members = &fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| field_member(i, f))
+        .collect::<Vec<_>>();
+    let indices = &(0..members.len()).collect::<Vec<_>>();
+    let len = members.len();
+    let out_of_bounds = quote! {
+        _ => panic!("index out of bounds: the len is {} but the index is {}", #len, idx)
     };
-    let field = &fields[0];
-    let field_ident = &field.ident;
-    quote!(self.#field_ident)
+    let (trait_impl, iter_fn) = if trait_name == "IndexMut" {
+        (
+            quote! {
+                #[inline]
+                fn index_mut(&mut self, idx: usize) -> &mut #field_type {
+                    match idx {
+                        #(#indices => &mut self.#members,)*
+                        #out_of_bounds
+                    }
+                }
+            },
+            quote! {
+                /// Returns an iterator over mutable references to the fields, in declaration
+                /// order.
+                #[inline]
+                pub fn iter_mut(&mut self) -> ::core::array::IntoIter<&mut #field_type, #len> {
+                    ::core::iter::IntoIterator::into_iter([#(&mut self.#members),*])
+                }
+            },
+        )
+    } else {
+        (
+            quote! {
+                type Output = #field_type;
+                #[inline]
+                fn index(&self, idx: usize) -> &#field_type {
+                    match idx {
+                        #(#indices => &self.#members,)*
+                        #out_of_bounds
+                    }
+                }
+            },
+            quote! {
+                /// Returns an iterator over references to the fields, in declaration order.
+                #[inline]
+                pub fn iter(&self) -> ::core::array::IntoIter<&#field_type, #len> {
+                    ::core::iter::IntoIterator::into_iter([#(&self.#members),*])
+                }
+            },
+        )
+    };
+    let trait_ident = Ident::new(trait_name, Span::call_site());
+    Ok(quote! {
+        impl#impl_generics ::core::ops::#trait_ident<usize> for #input_type#ty_generics #where_clause
+        {
+            #trait_impl
+        }
+
+        impl#impl_generics #input_type#ty_generics #where_clause {
+            #iter_fn
+        }
+    })
 }
@@ -1,13 +1,17 @@
 use crate::utils::{
-    add_extra_generic_param, field_idents, get_field_types, named_to_vec, number_idents,
-    unnamed_to_vec, RefType,
+    add_extra_generic_param, field_idents, get_attr_args, get_common_field_type, get_field_types,
+    is_flag, named_to_vec, number_idents, unnamed_to_vec, RefType,
 };
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
-use syn::{Data, DeriveInput, Field, Fields};
+use syn::{
+    parse::{Error, Result},
+    spanned::Spanned,
+    Data, DeriveInput, Field, Fields,
+};
 
 /// Provides the hook to expand `#[derive(Into)]` into an implementation of `Into`
-pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
     let (ref_type, _) = RefType::from_derive(trait_name);
     let input_type = &input.ident;
     let field_vec: Vec<_>;
@@ -25,6 +29,13 @@ pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
         },
         _ => panic!("Only structs can derive Into"),
     };
+    let array = match get_attr_args(&input.attrs, "into")? {
+        Some(args) => match args.as_slice() {
+            [arg] if is_flag(arg, "array") => true,
+            _ => return Err(Error::new(input.span(), "Proper syntax: #[into(array)]")),
+        },
+        None => false,
+    };
 
     let original_types = &get_field_types(&fields);
     let reference = ref_type.reference();
@@ -40,7 +51,7 @@ pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
         input.generics.split_for_impl()
     };
 
-    quote! {
+    let mut tokens = quote! {
         impl#impl_generics ::core::convert::From<#reference_with_lifetime #input_type#ty_generics> for
             (#(#reference_with_lifetime #original_types),*) #where_clause {
 
@@ -50,7 +61,28 @@ pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
                 (#(#reference original.#field_names),*)
             }
         }
+    };
+
+    if array {
+        let fields = match input.data {
+            Data::Struct(ref data_struct) => &data_struct.fields,
+            _ => unreachable!(),
+        };
+        let field_type = get_common_field_type(fields, input.span(), "#[into(array)]")?;
+        let len = field_names.len();
+        quote! {
+            impl#impl_generics ::core::convert::From<#reference_with_lifetime #input_type#ty_generics> for
+                [#reference_with_lifetime #field_type; #len] #where_clause {
+
+                #[inline]
+                fn from(original: #reference_with_lifetime #input_type#ty_generics) -> [#reference_with_lifetime #field_type; #len] {
+                    [#(#reference original.#field_names),*]
+                }
+            }
+        }
+        .to_tokens(&mut tokens);
     }
+    Ok(tokens)
 }
 
 fn tuple_field_names(fields: &[&Field]) -> Vec<TokenStream> {
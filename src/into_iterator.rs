@@ -1,81 +1,201 @@
+use crate::iterator_helpers::{impl_trait, item_type, same_item_bounds};
 use crate::utils::{
-    add_extra_generic_param, add_extra_ty_param_bound_ref, named_to_vec, unnamed_to_vec, RefType,
+    add_extra_generic_param, add_extra_where_clauses, get_attr_args, get_forwarded_fields, is_flag,
+    ForwardedField, RefType,
 };
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{Data, DeriveInput, Field, Fields, Ident};
+use syn::{
+    parse::{Error, Result},
+    parse_quote,
+    spanned::Spanned,
+    Data, DeriveInput, Ident, Member, NestedMeta, Type,
+};
 
-/// Provides the hook to expand `#[derive(Index)]` into an implementation of `From`
-pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
-    let (ref_type, trait_name) = RefType::from_derive(trait_name);
-    let trait_name = trait_name.trim_end_matches("Ref");
-    let trait_ident = Ident::new(trait_name, Span::call_site());
-    let trait_path = &quote!(::core::iter::#trait_ident);
-    let input_type = &input.ident;
-    let field_vec: Vec<&Field>;
-    let member = match input.data {
-        Data::Struct(ref data_struct) => match data_struct.fields {
-            Fields::Unnamed(ref fields) => {
-                field_vec = unnamed_to_vec(fields);
-                tuple_from_str(trait_name, &field_vec)
-            }
-            Fields::Named(ref fields) => {
-                field_vec = named_to_vec(fields);
-                struct_from_str(trait_name, &field_vec)
-            }
-            Fields::Unit => panic_one_field(trait_name),
-        },
-        _ => panic_one_field(trait_name),
+/// Provides the hook to expand `#[derive(IntoIterator)]` into implementations of `IntoIterator`
+/// for the type itself and for references to it, as selected with `#[into_iterator(...)]`
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
+    let fields = get_forwarded_fields(input, trait_name, "into_iterator")?;
+    let mut args = get_attr_args(&input.attrs, "into_iterator")?.unwrap_or_default();
+    for (_, _, _, field_args) in &fields {
+        args.extend(field_args.iter().cloned());
+    }
+    let ref_types = parse_ref_types(&args)?;
+
+    let iter_enum = match input.data {
+        Data::Enum(_) => Some(Ident::new(
+            &format!("{}IntoIter", input.ident),
+            Span::call_site(),
+        )),
+        _ => None,
     };
-    let field_type = &field_vec[0].ty;
+    let mut tokens = match iter_enum {
+        Some(ref iter_name) => iter_enum_definition(input, &fields, iter_name),
+        None => TokenStream::new(),
+    };
+    for ref_type in ref_types {
+        tokens.extend(impl_into_iterator(
+            input,
+            &fields,
+            ref_type,
+            iter_enum.as_ref(),
+        ));
+    }
+    Ok(tokens)
+}
 
+/// Returns which of the type itself and references to it should implement `IntoIterator`. Without
+/// any arguments this is only the type itself.
+fn parse_ref_types(args: &[NestedMeta]) -> Result<Vec<RefType>> {
+    let mut flags = [false; 3];
+    for arg in args {
+        match ["owned", "ref", "ref_mut"]
+            .iter()
+            .position(|flag| is_flag(arg, flag))
+        {
+            Some(i) => flags[i] = true,
+            None => {
+                return Err(Error::new(
+                    arg.span(),
+                    "Proper syntax: #[into_iterator(owned, ref, ref_mut)]",
+                ))
+            }
+        }
+    }
+    if args.is_empty() {
+        flags[0] = true;
+    }
+    Ok([RefType::No, RefType::Ref, RefType::Mut]
+        .iter()
+        .zip(&flags)
+        .filter(|(_, enabled)| **enabled)
+        .map(|(ref_type, _)| *ref_type)
+        .collect())
+}
+
+/// Implements `IntoIterator` for the type, or a reference to it, by converting the forwarded field
+/// into an iterator. For enums this iterator is wrapped in the variant of `iter_enum` that matches
+/// the active variant.
+fn impl_into_iterator(
+    input: &DeriveInput,
+    fields: &[ForwardedField],
+    ref_type: RefType,
+    iter_enum: Option<&Ident>,
+) -> TokenStream {
+    let trait_path = &quote!(::core::iter::IntoIterator);
+    let input_type = &input.ident;
     let reference = ref_type.reference();
-    let lifetime = ref_type.lifetime();
     let reference_with_lifetime = ref_type.reference_with_lifetime();
 
-    let generics_impl;
-    let generics = add_extra_ty_param_bound_ref(&input.generics, trait_path, ref_type);
-    let (_, ty_generics, where_clause) = generics.split_for_impl();
-    let (impl_generics, _, _) = if ref_type.is_ref() {
-        generics_impl = add_extra_generic_param(&generics, lifetime.clone());
-        generics_impl.split_for_impl()
-    } else {
-        generics.split_for_impl()
+    let types: Vec<Type> = fields
+        .iter()
+        .map(|(_, _, field, _)| {
+            let ty = &field.ty;
+            parse_quote!(#reference_with_lifetime #ty)
+        })
+        .collect();
+    let type_refs: Vec<_> = types.iter().collect();
+    let item = &item_type(&type_refs, trait_path);
+    let where_clauses = same_item_bounds(&type_refs, trait_path, item);
+    let mut generics = add_extra_where_clauses(&input.generics, quote!(where #where_clauses));
+    if ref_type.is_ref() {
+        generics = add_extra_generic_param(&generics, ref_type.lifetime());
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let (into_iter_type, body) = match iter_enum {
+        Some(iter_name) => {
+            let arms = fields
+                .iter()
+                .zip(&types)
+                .map(|((variant, member, _, _), ty)| {
+                    let pattern = quote!(#input_type::#variant { #member: __deriveMoreField, .. });
+                    let into_iter = quote!(<#ty as #trait_path>::into_iter(__deriveMoreField));
+                    quote!(#pattern => #iter_name::#variant(#into_iter))
+                });
+            (
+                quote!(#iter_name<#(<#types as #trait_path>::IntoIter),*>),
+                quote! {
+                    match self {
+                        #(#arms),*
+                    }
+                },
+            )
+        }
+        None => {
+            let member = &fields[0].1;
+            let ty = &types[0];
+            (
+                quote!(<#ty as #trait_path>::IntoIter),
+                quote!(<#ty as #trait_path>::into_iter(#reference self.#member)),
+            )
+        }
     };
-    // let generics = add_extra_ty_param_bound(&input.generics, trait_path);
-    let casted_trait = &quote!(<#reference_with_lifetime #field_type as #trait_path>);
     quote! {
         impl#impl_generics #trait_path for #reference_with_lifetime #input_type#ty_generics #where_clause
         {
-            type Item = #casted_trait::Item;
-            type IntoIter = #casted_trait::IntoIter;
+            type Item = #item;
+            type IntoIter = #into_iter_type;
             #[inline]
             fn into_iter(self) -> Self::IntoIter {
-                #casted_trait::into_iter(#reference #member)
+                #body
             }
         }
     }
 }
 
-fn panic_one_field(trait_name: &str) -> ! {
-    panic!(format!(
-        "Only structs with one field can derive({})",
-        trait_name
-    ))
-}
+/// Generates the `{Enum}IntoIter` enum for an enum, which mirrors its variants and holds the
+/// iterator of the active variant. It implements the iterator traits by forwarding to that
+/// iterator, so it doesn't need to know the types of the iterators.
+fn iter_enum_definition(
+    input: &DeriveInput,
+    fields: &[ForwardedField],
+    iter_name: &Ident,
+) -> TokenStream {
+    let input_type = &input.ident;
+    let vis = &input.vis;
+    let variants: Vec<_> = fields.iter().map(|(variant, _, _, _)| *variant).collect();
+    let iter_params: Vec<_> = (0..fields.len())
+        .map(|i| Ident::new(&format!("I{}", i), Span::call_site()))
+        .collect();
+    let iter_param_types: Vec<Type> = iter_params
+        .iter()
+        .map(|param| parse_quote!(#param))
+        .collect();
+    let iter_fields: Vec<_> = variants
+        .iter()
+        .zip(&iter_param_types)
+        .map(|(variant, ty)| (*variant, Member::Unnamed(0.into()), ty))
+        .collect();
+    let iter_generics = parse_quote!(<#(#iter_params),*>);
+    let iter_impls = [
+        "Iterator",
+        "DoubleEndedIterator",
+        "ExactSizeIterator",
+        "FusedIterator",
+    ]
+    .iter()
+    .map(|trait_name| impl_trait(iter_name, &iter_generics, &iter_fields, trait_name, true));
 
-fn tuple_from_str<'a>(trait_name: &str, fields: &[&'a Field]) -> (TokenStream) {
-    if fields.len() != 1 {
-        panic_one_field(trait_name)
-    };
-    quote!(self.0)
-}
+    let iter_doc = format!(
+        "An iterator over the values of a [`{}`], created by its `IntoIterator` implementations.",
+        input_type
+    );
+    let variant_docs = variants.iter().map(|variant| {
+        format!(
+            "Iterates over the values of a [`{}::{}`].",
+            input_type,
+            variant.unwrap()
+        )
+    });
+    quote! {
+        #[doc = #iter_doc]
+        #[derive(Clone, Debug)]
+        #vis enum #iter_name<#(#iter_params),*> {
+            #(#[doc = #variant_docs] #variants(#iter_params)),*
+        }
 
-fn struct_from_str<'a>(trait_name: &str, fields: &[&'a Field]) -> TokenStream {
-    if fields.len() != 1 {
-        panic_one_field(trait_name)
-    };
-    let field = &fields[0];
-    let field_ident = &field.ident;
-    quote!(self.#field_ident)
+        #(#iter_impls)*
+    }
 }
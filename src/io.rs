@@ -0,0 +1,242 @@
+use crate::utils::{add_extra_where_clauses, forward_call, get_forwarded_fields};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse::{Error, Result},
+    spanned::Spanned,
+    DeriveInput, Ident,
+};
+
+/// Provides the hook to expand `#[derive(Read)]`, `#[derive(Write)]`, `#[derive(BufRead)]`,
+/// `#[derive(Seek)]` and `#[derive(FmtWrite)]` into an implementation of the `std::io` trait or
+/// of `core::fmt::Write`, which forwards every method to the field of a struct or to the field of
+/// the active variant of an enum
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
+    let (trait_path, attr_name) = match trait_name {
+        "FmtWrite" => (quote!(::core::fmt::Write), "fmt_write"),
+        _ => {
+            let trait_ident = Ident::new(trait_name, Span::call_site());
+            (quote!(::std::io::#trait_ident), "io")
+        }
+    };
+    let mut fields = vec![];
+    for (variant, member, field, args) in get_forwarded_fields(input, trait_name, attr_name)? {
+        if let Some(arg) = args.first() {
+            return Err(Error::new(
+                arg.span(),
+                format!("Proper syntax: #[{}]", attr_name),
+            ));
+        }
+        fields.push((variant, member, &field.ty));
+    }
+
+    let mut seen = vec![];
+    let mut bounds = vec![];
+    for (_, _, ty) in &fields {
+        let ty_string = quote!(#ty).to_string();
+        if !seen.contains(&ty_string) {
+            bounds.push(quote!(#ty: #trait_path));
+            seen.push(ty_string);
+        }
+    }
+    let generics = add_extra_where_clauses(&input.generics, quote!(where #(#bounds),*));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let input_type = &input.ident;
+
+    let methods = methods(trait_name)
+        .into_iter()
+        .map(|(signature, method, args)| {
+            let method = Ident::new(method, Span::call_site());
+            let args: Vec<_> = args
+                .iter()
+                .map(|arg| Ident::new(arg, Span::call_site()))
+                .collect();
+            let body = forward_call(
+                input_type,
+                &fields,
+                &quote!(&mut),
+                |ty, field| quote!(<#ty as #trait_path>::#method(#field #(, #args)*)),
+            );
+            quote! {
+                #[inline]
+                #signature {
+                    #body
+                }
+            }
+        });
+    Ok(quote! {
+        impl#impl_generics #trait_path for #input_type#ty_generics #where_clause
+        {
+            #(#methods)*
+        }
+    })
+}
+
+/// Returns the signature, name and argument names of every method of the trait that is forwarded.
+/// The unstable methods of the traits are left to their default implementations, and the methods
+/// that are missing from the compiler are left out.
+fn methods(trait_name: &str) -> Vec<(TokenStream, &'static str, Vec<&'static str>)> {
+    let io_result = quote!(::std::io::Result);
+    let mut methods = match trait_name {
+        "Read" => vec![
+            (
+                quote!(fn read(&mut self, buf: &mut [u8]) -> #io_result<usize>),
+                "read",
+                vec!["buf"],
+            ),
+            (
+                quote! {
+                    fn read_vectored(
+                        &mut self,
+                        bufs: &mut [::std::io::IoSliceMut<'_>],
+                    ) -> #io_result<usize>
+                },
+                "read_vectored",
+                vec!["bufs"],
+            ),
+            (
+                quote! {
+                    fn read_to_end(&mut self, buf: &mut ::std::vec::Vec<u8>) -> #io_result<usize>
+                },
+                "read_to_end",
+                vec!["buf"],
+            ),
+            (
+                quote! {
+                    fn read_to_string(
+                        &mut self,
+                        buf: &mut ::std::string::String,
+                    ) -> #io_result<usize>
+                },
+                "read_to_string",
+                vec!["buf"],
+            ),
+            (
+                quote!(fn read_exact(&mut self, buf: &mut [u8]) -> #io_result<()>),
+                "read_exact",
+                vec!["buf"],
+            ),
+        ],
+        "Write" => vec![
+            (
+                quote!(fn write(&mut self, buf: &[u8]) -> #io_result<usize>),
+                "write",
+                vec!["buf"],
+            ),
+            (
+                quote! {
+                    fn write_vectored(&mut self, bufs: &[::std::io::IoSlice<'_>]) -> #io_result<usize>
+                },
+                "write_vectored",
+                vec!["bufs"],
+            ),
+            (
+                quote!(fn flush(&mut self) -> #io_result<()>),
+                "flush",
+                vec![],
+            ),
+            (
+                quote!(fn write_all(&mut self, buf: &[u8]) -> #io_result<()>),
+                "write_all",
+                vec!["buf"],
+            ),
+            (
+                quote!(fn write_fmt(&mut self, fmt: ::core::fmt::Arguments<'_>) -> #io_result<()>),
+                "write_fmt",
+                vec!["fmt"],
+            ),
+        ],
+        "BufRead" => vec![
+            (
+                quote!(fn fill_buf(&mut self) -> #io_result<&[u8]>),
+                "fill_buf",
+                vec![],
+            ),
+            (
+                quote!(fn consume(&mut self, amt: usize)),
+                "consume",
+                vec!["amt"],
+            ),
+            (
+                quote! {
+                    fn read_until(
+                        &mut self,
+                        byte: u8,
+                        buf: &mut ::std::vec::Vec<u8>,
+                    ) -> #io_result<usize>
+                },
+                "read_until",
+                vec!["byte", "buf"],
+            ),
+            (
+                quote! {
+                    fn read_line(&mut self, buf: &mut ::std::string::String) -> #io_result<usize>
+                },
+                "read_line",
+                vec!["buf"],
+            ),
+            (
+                quote!(fn skip_until(&mut self, byte: u8) -> #io_result<usize>),
+                "skip_until",
+                vec!["byte"],
+            ),
+        ],
+        "Seek" => vec![
+            (
+                quote!(fn seek(&mut self, pos: ::std::io::SeekFrom) -> #io_result<u64>),
+                "seek",
+                vec!["pos"],
+            ),
+            (
+                quote!(fn rewind(&mut self) -> #io_result<()>),
+                "rewind",
+                vec![],
+            ),
+            (
+                quote!(fn stream_position(&mut self) -> #io_result<u64>),
+                "stream_position",
+                vec![],
+            ),
+            (
+                quote!(fn seek_relative(&mut self, offset: i64) -> #io_result<()>),
+                "seek_relative",
+                vec!["offset"],
+            ),
+        ],
+        _ => vec![
+            (
+                quote!(fn write_str(&mut self, s: &str) -> ::core::fmt::Result),
+                "write_str",
+                vec!["s"],
+            ),
+            (
+                quote!(fn write_char(&mut self, c: char) -> ::core::fmt::Result),
+                "write_char",
+                vec!["c"],
+            ),
+            (
+                quote! {
+                    fn write_fmt(&mut self, args: ::core::fmt::Arguments<'_>) -> ::core::fmt::Result
+                },
+                "write_fmt",
+                vec!["args"],
+            ),
+        ],
+    };
+    methods.retain(|(_, method, _)| is_available(method));
+    methods
+}
+
+/// Returns whether the compiler has the method, which the build script detects for the methods
+/// that were stabilized later than the oldest supported Rust version.
+fn is_available(method: &str) -> bool {
+    match method {
+        "read_vectored" | "write_vectored" => cfg!(derive_more_io_vectored),
+        "stream_position" => cfg!(derive_more_stream_position),
+        "rewind" => cfg!(derive_more_rewind),
+        "seek_relative" => cfg!(derive_more_seek_relative),
+        "skip_until" => cfg!(derive_more_skip_until),
+        _ => true,
+    }
+}
@@ -0,0 +1,54 @@
+use crate::utils::variant_method_name;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Error, Result},
+    spanned::Spanned,
+    Data, DeriveInput, Ident,
+};
+
+/// Provides the hook to expand `#[derive(IsVariant)]` into `is_*` methods for every variant
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
+    let data_enum = match input.data {
+        Data::Enum(ref data_enum) => data_enum,
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                format!("Only enums can derive({})", trait_name),
+            ))
+        }
+    };
+    let enum_name = &input.ident;
+
+    let mut methods = vec![];
+    for variant in &data_enum.variants {
+        let name = match variant_method_name(variant, "is_variant")? {
+            Some(name) => name,
+            None => continue,
+        };
+        let variant_ident = &variant.ident;
+        let fn_name = Ident::new(&format!("is_{}", name), variant_ident.span());
+        let doc = format!(
+            "Returns `true` if this value is of variant `{}::{}`.",
+            enum_name, variant_ident
+        );
+        methods.push(quote! {
+            #[doc = #doc]
+            #[inline]
+            #[allow(unreachable_patterns)]
+            pub const fn #fn_name(&self) -> bool {
+                match *self {
+                    #enum_name::#variant_ident { .. } => true,
+                    _ => false,
+                }
+            }
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl#impl_generics #enum_name#ty_generics #where_clause {
+            #(#methods)*
+        }
+    })
+}
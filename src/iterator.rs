@@ -1,65 +1,28 @@
-use crate::utils::{add_extra_ty_param_bound, named_to_vec, unnamed_to_vec};
-use proc_macro2::{Span, TokenStream};
-use quote::quote;
-use syn::{Data, DeriveInput, Field, Fields, Ident};
+use crate::iterator_helpers::impl_trait;
+use crate::utils::get_forwarded_fields;
+use proc_macro2::TokenStream;
+use syn::{
+    parse::{Error, Result},
+    spanned::Spanned,
+    DeriveInput,
+};
 
-/// Provides the hook to expand `#[derive(Index)]` into an implementation of `From`
-pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
-    let trait_ident = Ident::new(trait_name, Span::call_site());
-    let trait_path = &quote!(::core::iter::#trait_ident);
-    let input_type = &input.ident;
-    let field_vec: Vec<&Field>;
-    let member = match input.data {
-        Data::Struct(ref data_struct) => match data_struct.fields {
-            Fields::Unnamed(ref fields) => {
-                field_vec = unnamed_to_vec(fields);
-                tuple_from_str(trait_name, &field_vec)
-            }
-            Fields::Named(ref fields) => {
-                field_vec = named_to_vec(fields);
-                struct_from_str(trait_name, &field_vec)
-            }
-            Fields::Unit => panic_one_field(trait_name),
-        },
-        _ => panic_one_field(trait_name),
-    };
-    let field_type = &field_vec[0].ty;
-
-    let generics = add_extra_ty_param_bound(&input.generics, trait_path);
-    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
-    // let generics = add_extra_ty_param_bound(&input.generics, trait_path);
-    let casted_trait = &quote!(<#field_type as #trait_path>);
-    quote! {
-        impl#impl_generics #trait_path for #input_type#ty_generics #where_clause
-        {
-            type Item = #casted_trait::Item;
-            #[inline]
-            fn next(&mut self) -> Option<Self::Item> {
-                #casted_trait::next(&mut #member)
-            }
+/// Provides the hook to expand `#[derive(Iterator)]`, `#[derive(DoubleEndedIterator)]`,
+/// `#[derive(ExactSizeIterator)]` and `#[derive(FusedIterator)]` into an implementation of that
+/// trait, which forwards to the field of a struct or to the field of the active variant of an enum
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
+    let mut fields = vec![];
+    for (variant, member, field, args) in get_forwarded_fields(input, trait_name, "iterator")? {
+        if let Some(arg) = args.first() {
+            return Err(Error::new(arg.span(), "Proper syntax: #[iterator]"));
         }
+        fields.push((variant, member, &field.ty));
     }
-}
-
-fn panic_one_field(trait_name: &str) -> ! {
-    panic!(format!(
-        "Only structs with one field can derive({})",
-        trait_name
+    Ok(impl_trait(
+        &input.ident,
+        &input.generics,
+        &fields,
+        trait_name,
+        false,
     ))
 }
-
-fn tuple_from_str<'a>(trait_name: &str, fields: &[&'a Field]) -> (TokenStream) {
-    if fields.len() != 1 {
-        panic_one_field(trait_name)
-    };
-    quote!(self.0)
-}
-
-fn struct_from_str<'a>(trait_name: &str, fields: &[&'a Field]) -> TokenStream {
-    if fields.len() != 1 {
-        panic_one_field(trait_name)
-    };
-    let field = &fields[0];
-    let field_ident = &field.ident;
-    quote!(self.#field_ident)
-}
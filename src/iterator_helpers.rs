@@ -0,0 +1,153 @@
+use crate::utils::{add_extra_where_clauses, forward_call, ForwardedType};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Generics, Ident, Type};
+
+/// Implements one of the iterator traits for a type that forwards to the given fields. This is
+/// also used for the iterator enum generated by `#[derive(IntoIterator)]` for enums. The methods
+/// that take `self` by value, like `fold`, are only forwarded with `by_value`, because moving the
+/// field out of `self` doesn't compile when the type implements `Drop`.
+pub fn impl_trait(
+    input_type: &Ident,
+    generics: &Generics,
+    fields: &[ForwardedType],
+    trait_name: &str,
+    by_value: bool,
+) -> TokenStream {
+    let trait_ident = Ident::new(trait_name, Span::call_site());
+    let trait_path = &quote!(::core::iter::#trait_ident);
+    let types: Vec<_> = fields.iter().map(|(_, _, ty)| *ty).collect();
+    let item = &item_type(&types, &quote!(::core::iter::Iterator));
+    let where_clauses = same_item_bounds(&types, trait_path, item);
+    let generics = add_extra_where_clauses(generics, quote!(where #where_clauses));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = generics.split_for_impl();
+
+    let forward = |reference: TokenStream, method: &str, args: &[&str]| {
+        let method = Ident::new(method, Span::call_site());
+        let args: Vec<_> = args
+            .iter()
+            .map(|arg| Ident::new(arg, Span::call_site()))
+            .collect();
+        forward_call(
+            input_type,
+            fields,
+            &reference,
+            |ty, field| quote!(<#ty as #trait_path>::#method(#field #(, #args)*)),
+        )
+    };
+    let body = match trait_name {
+        "DoubleEndedIterator" => {
+            let next_back = forward(quote!(&mut), "next_back", &[]);
+            let nth_back = forward(quote!(&mut), "nth_back", &["n"]);
+            let mut methods = quote! {
+                #[inline]
+                fn next_back(&mut self) -> ::core::option::Option<#item> {
+                    #next_back
+                }
+                #[inline]
+                fn nth_back(&mut self, n: usize) -> ::core::option::Option<#item> {
+                    #nth_back
+                }
+            };
+            if by_value {
+                let rfold = forward(quote!(), "rfold", &["init", "f"]);
+                methods.extend(quote! {
+                    #[inline]
+                    fn rfold<__FoldB, __FoldF>(self, init: __FoldB, f: __FoldF) -> __FoldB
+                    where
+                        __FoldF: ::core::ops::FnMut(__FoldB, #item) -> __FoldB,
+                    {
+                        #rfold
+                    }
+                });
+            }
+            methods
+        }
+        "ExactSizeIterator" => {
+            let len = forward(quote!(&), "len", &[]);
+            quote! {
+                #[inline]
+                fn len(&self) -> usize {
+                    #len
+                }
+            }
+        }
+        // A marker trait without any methods
+        "FusedIterator" => quote!(),
+        _ => {
+            let next = forward(quote!(&mut), "next", &[]);
+            let size_hint = forward(quote!(&), "size_hint", &[]);
+            let nth = forward(quote!(&mut), "nth", &["n"]);
+            let mut methods = quote! {
+                type Item = #item;
+                #[inline]
+                fn next(&mut self) -> ::core::option::Option<#item> {
+                    #next
+                }
+                #[inline]
+                fn size_hint(&self) -> (usize, ::core::option::Option<usize>) {
+                    #size_hint
+                }
+                #[inline]
+                fn nth(&mut self, n: usize) -> ::core::option::Option<#item> {
+                    #nth
+                }
+            };
+            if by_value {
+                let count = forward(quote!(), "count", &[]);
+                let fold = forward(quote!(), "fold", &["init", "f"]);
+                methods.extend(quote! {
+                    #[inline]
+                    fn count(self) -> usize {
+                        #count
+                    }
+                    #[inline]
+                    fn fold<__FoldB, __FoldF>(self, init: __FoldB, f: __FoldF) -> __FoldB
+                    where
+                        __FoldF: ::core::ops::FnMut(__FoldB, #item) -> __FoldB,
+                    {
+                        #fold
+                    }
+                });
+            }
+            methods
+        }
+    };
+    quote! {
+        impl#impl_generics #trait_path for #input_type#ty_generics #where_clause
+        {
+            #body
+        }
+    }
+}
+
+/// Returns the `Item` type of the first of the types, which implement `trait_path`.
+pub fn item_type(types: &[&Type], trait_path: &TokenStream) -> TokenStream {
+    let first = types[0];
+    quote!(<#first as #trait_path>::Item)
+}
+
+/// Returns the where clauses that require all of the types to implement `trait_path`, with `item`
+/// as their `Item` type. Types that occur multiple times only get a single clause.
+pub fn same_item_bounds(
+    types: &[&Type],
+    trait_path: &TokenStream,
+    item: &TokenStream,
+) -> TokenStream {
+    let mut seen = vec![];
+    let mut bounds = vec![];
+    for ty in types {
+        let ty_string = quote!(#ty).to_string();
+        if seen.contains(&ty_string) {
+            continue;
+        }
+        if seen.is_empty() {
+            bounds.push(quote!(#ty: #trait_path));
+        } else {
+            bounds.push(quote!(#ty: #trait_path<Item = #item>));
+        }
+        seen.push(ty_string);
+    }
+    quote!(#(#bounds),*)
+}
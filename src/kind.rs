@@ -0,0 +1,98 @@
+use crate::utils::{add_extra_generic_param, get_attr_args, get_derive_arg, get_str_arg};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse::{Error, Result},
+    spanned::Spanned,
+    Data, DeriveInput, Ident, Path,
+};
+
+/// The traits that are always derived for the mirror enum
+const DEFAULT_DERIVES: &[&str] = &["Clone", "Copy", "Debug", "PartialEq", "Eq"];
+
+/// Provides the hook to expand `#[derive(Kind)]` into a fieldless mirror enum of the input enum,
+/// together with a `kind` method and a `From` implementation to convert to it
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
+    let data_enum = match input.data {
+        Data::Enum(ref data_enum) => data_enum,
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                format!("Only enums can derive({})", trait_name),
+            ))
+        }
+    };
+    let enum_name = &input.ident;
+    let vis = &input.vis;
+
+    let mut kind_name = Ident::new(&format!("{}Kind", enum_name), Span::call_site());
+    let mut derives: Vec<Path> = vec![];
+    for arg in get_attr_args(&input.attrs, "kind")?.unwrap_or_default() {
+        if let Some(name) = get_str_arg(&arg, "name")? {
+            kind_name = name.parse()?;
+            continue;
+        }
+        match get_derive_arg(&arg)? {
+            Some(paths) => {
+                // Skip the traits that are always derived, as deriving them twice conflicts
+                for path in paths {
+                    let name = path.segments.last().map(|s| s.ident.to_string());
+                    let is_default = match name {
+                        Some(ref name) => DEFAULT_DERIVES.contains(&name.as_str()),
+                        None => false,
+                    };
+                    if !is_default && !derives.contains(&path) {
+                        derives.push(path);
+                    }
+                }
+            }
+            None => {
+                return Err(Error::new(
+                    arg.span(),
+                    "Proper syntax: #[kind(derive(...))] or #[kind(name = \"...\")]",
+                ))
+            }
+        }
+    }
+
+    let variant_idents: Vec<_> = data_enum.variants.iter().map(|v| &v.ident).collect();
+    // Keep the documentation of the variants, so the kinds are documented as well
+    let variant_docs = data_enum.variants.iter().map(|v| {
+        let docs = v.attrs.iter().filter(|a| a.path.is_ident("doc"));
+        quote!(#(#docs)*)
+    });
+    let kind_doc = format!("The variants of [`{}`], without their fields.", enum_name);
+    let kind_fn_doc = format!("Returns the [`{}`] of this value.", kind_name);
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let lifetime = quote!('__deriveMoreLifetime);
+    let ref_generics = add_extra_generic_param(&input.generics, lifetime.clone());
+    let (ref_impl_generics, _, _) = ref_generics.split_for_impl();
+
+    Ok(quote! {
+        #[doc = #kind_doc]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq #(, #derives)*)]
+        #vis enum #kind_name {
+            #(#variant_docs #variant_idents),*
+        }
+
+        impl#impl_generics #enum_name#ty_generics #where_clause {
+            #[doc = #kind_fn_doc]
+            #[inline]
+            #vis const fn kind(&self) -> #kind_name {
+                match *self {
+                    #(#enum_name::#variant_idents { .. } => #kind_name::#variant_idents),*
+                }
+            }
+        }
+
+        impl#ref_impl_generics ::core::convert::From<&#lifetime #enum_name#ty_generics> for #kind_name
+            #where_clause
+        {
+            #[inline]
+            fn from(value: &#lifetime #enum_name#ty_generics) -> #kind_name {
+                value.kind()
+            }
+        }
+    })
+}
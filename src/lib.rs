@@ -70,12 +70,36 @@
 //! 2. [`Into`]
 //! 3. [`FromStr`]
 //! 4. [`TryInto`]
+//! 5. [`AsRef`] and [`AsMut`]
+//! 6. [`Borrow`] and [`BorrowMut`]
 //!
 //! ### Formatting traits
 //! These traits are used for converting a struct to a string in different ways.
 //!
 //! 1. `Display`-like, contains [`Display`], [`Binary`], [`Octal`], [`LowerHex`], [`UpperHex`],
 //!    [`LowerExp`], [`UpperExp`], [`Pointer`]
+//! 2. `DebugCustom`, which derives [`Debug`] with support for skipping, redacting and
+//!    customizing the formatting of fields
+//!
+//! ### Error handling
+//! This derive is used to implement the [`Error`] trait for your error types.
+//!
+//! 1. [`Error`]
+//!
+//! ### Default values
+//! This derive is used to implement the [`Default`] trait with custom values for fields.
+//!
+//! 1. `DefaultCustom`, which derives [`Default`] with `#[default(...)]` values for fields and
+//!    `#[default]` to select the variant of an enum
+//!
+//! ### Comparison traits
+//! These traits are used to compare and hash values.
+//!
+//! 1. `PartialEqInner` and `PartialOrdInner`, which derive [`PartialEq`] and [`PartialOrd`]
+//!    between a newtype and the type of its field, in both directions
+//! 2. `PartialEqCustom`, `EqCustom`, `HashCustom`, `PartialOrdCustom` and `OrdCustom`, which
+//!    derive [`PartialEq`], [`Eq`], [`Hash`], [`PartialOrd`] and [`Ord`] with support for
+//!    skipping fields, comparing fields by a key and changing the order of the fields
 //!
 //! ### Operators
 //! These are traits that can be used for operator overloading.
@@ -92,11 +116,39 @@
 //! 9. `MulAssign`-like, contains [`MulAssign`], [`DivAssign`], [`RemAssign`], [`ShrAssign`] and
 //!    [`ShlAssign`]
 //!
+//! ### Iterators
+//! These traits are used to iterate over a type, by forwarding to a field that can be iterated
+//! over.
+//!
+//! 1. [`Iterator`], together with [`DoubleEndedIterator`], [`ExactSizeIterator`] and
+//!    [`FusedIterator`]
+//! 2. [`IntoIterator`]
+//! 3. [`FromIterator`] and [`Extend`]
+//!
+//! ### I/O
+//! These traits are used to read from or write to a type, by forwarding to a field that can be
+//! read from or written to. They are only available with the `std` feature.
+//!
+//! 1. [`Read`], together with [`BufRead`] and [`Seek`]
+//! 2. [`Write`]
+//! 3. `FmtWrite`, which derives [`fmt::Write`]
+//!
 //! ### Static methods
 //! These don't derive traits, but derive static methods instead.
 //!
 //! 1. `Constructor`, this derives a `new` method that can be used as a constructor. This is very
 //!    basic if you need more customization for your constructor, check out the [`derive-new`] crate.
+//! 2. `IsVariant`, for each variant `foo` of an enum type, derives a `is_foo` method.
+//! 3. `Unwrap`, `TryUnwrap` and `AsVariant`, for each variant `foo` of an enum type, derive
+//!    `unwrap_foo`, `try_unwrap_foo` and `as_foo`/`as_foo_mut` methods respectively.
+//! 4. `Kind`, generates a fieldless enum that mirrors the variants of an enum type, together
+//!    with a `kind` method to get it.
+//! 5. `Variants`, derives a `variant_name` method for an enum type, and constants and methods
+//!    to iterate over and index the variants of a fieldless enum type.
+//! 6. `VariantStructs`, generates a struct for every variant with named fields of an enum type,
+//!    together with `From` and `TryFrom` implementations to convert between them.
+//! 7. `Getters`, derives a getter method for every field of a struct, and optionally a `*_mut`
+//!    method that returns a mutable reference to it.
 //!
 //!
 //! ## Generated code
@@ -121,6 +173,23 @@
 //! 13. [`#[derive(AddAssign)]`](https://jeltef.github.io/derive_more/derive_more/add_assign.html)
 //! 14. [`#[derive(MulAssign)]`](https://jeltef.github.io/derive_more/derive_more/mul_assign.html)
 //! 15. [`#[derive(Constructor)]`](https://jeltef.github.io/derive_more/derive_more/constructor.html)
+//! 16. [`#[derive(DebugCustom)]`](https://jeltef.github.io/derive_more/derive_more/debug.html)
+//! 17. [`#[derive(Error)]`](https://jeltef.github.io/derive_more/derive_more/error.html)
+//! 18. [`#[derive(AsRef)]`](https://jeltef.github.io/derive_more/derive_more/as_ref.html)
+//! 19. [`#[derive(IsVariant)]`](https://jeltef.github.io/derive_more/derive_more/is_variant.html)
+//! 20. [`#[derive(Unwrap)]`](https://jeltef.github.io/derive_more/derive_more/unwrap.html)
+//! 21. [`#[derive(Kind)]`](https://jeltef.github.io/derive_more/derive_more/kind.html)
+//! 22. [`#[derive(Variants)]`](https://jeltef.github.io/derive_more/derive_more/variants.html)
+//! 23. [`#[derive(VariantStructs)]`](https://jeltef.github.io/derive_more/derive_more/variant_structs.html)
+//! 24. [`#[derive(Iterator)]`](https://jeltef.github.io/derive_more/derive_more/iterator.html)
+//! 25. [`#[derive(IntoIterator)]`](https://jeltef.github.io/derive_more/derive_more/into_iterator.html)
+//! 26. [`#[derive(FromIterator)]`](https://jeltef.github.io/derive_more/derive_more/from_iterator.html)
+//! 27. [`#[derive(Read)]`](https://jeltef.github.io/derive_more/derive_more/io.html)
+//! 28. [`#[derive(Borrow)]`](https://jeltef.github.io/derive_more/derive_more/borrow.html)
+//! 29. [`#[derive(PartialEqInner)]`](https://jeltef.github.io/derive_more/derive_more/partial_eq_inner.html)
+//! 30. [`#[derive(PartialEqCustom)]`](https://jeltef.github.io/derive_more/derive_more/cmp.html)
+//! 31. [`#[derive(DefaultCustom)]`](https://jeltef.github.io/derive_more/derive_more/default_custom.html)
+//! 32. [`#[derive(Getters)]`](https://jeltef.github.io/derive_more/derive_more/getters.html)
 //!
 //! If you want to be sure what code is generated for your specific type I recommend using the
 //! [`cargo-expand`] utility.
@@ -159,6 +228,16 @@
 //! [`Into`]: https://doc.rust-lang.org/core/convert/trait.Into.html
 //! [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
 //! [`TryInto`]: https://doc.rust-lang.org/core/convert/trait.TryInto.html
+//! [`AsRef`]: https://doc.rust-lang.org/core/convert/trait.AsRef.html
+//! [`AsMut`]: https://doc.rust-lang.org/core/convert/trait.AsMut.html
+//! [`Borrow`]: https://doc.rust-lang.org/core/borrow/trait.Borrow.html
+//! [`BorrowMut`]: https://doc.rust-lang.org/core/borrow/trait.BorrowMut.html
+//! [`PartialEq`]: https://doc.rust-lang.org/core/cmp/trait.PartialEq.html
+//! [`PartialOrd`]: https://doc.rust-lang.org/core/cmp/trait.PartialOrd.html
+//! [`Eq`]: https://doc.rust-lang.org/core/cmp/trait.Eq.html
+//! [`Hash`]: https://doc.rust-lang.org/core/hash/trait.Hash.html
+//! [`Ord`]: https://doc.rust-lang.org/core/cmp/trait.Ord.html
+//! [`Default`]: https://doc.rust-lang.org/core/default/trait.Default.html
 //! [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
 //! [`Binary`]: https://doc.rust-lang.org/std/fmt/trait.Binary.html
 //! [`Octal`]: https://doc.rust-lang.org/std/fmt/trait.Octal.html
@@ -167,6 +246,8 @@
 //! [`LowerExp`]: https://doc.rust-lang.org/std/fmt/trait.LowerExp.html
 //! [`UpperExp`]: https://doc.rust-lang.org/std/fmt/trait.UpperExp.html
 //! [`Pointer`]: https://doc.rust-lang.org/std/fmt/trait.Pointer.html
+//! [`Debug`]: https://doc.rust-lang.org/std/fmt/trait.Debug.html
+//! [`Error`]: https://doc.rust-lang.org/std/error/trait.Error.html
 //! [`Index`]: https://doc.rust-lang.org/std/ops/trait.Index.html
 //! [`Deref`]: https://doc.rust-lang.org/std/ops/trait.Deref.html
 //! [`Not`]: https://doc.rust-lang.org/std/ops/trait.Not.html
@@ -193,6 +274,18 @@
 //! [`RemAssign`]: https://doc.rust-lang.org/std/ops/trait.RemAssign.html
 //! [`ShrAssign`]: https://doc.rust-lang.org/std/ops/trait.ShrAssign.html
 //! [`ShlAssign`]: https://doc.rust-lang.org/std/ops/trait.ShlAssign.html
+//! [`Iterator`]: https://doc.rust-lang.org/core/iter/trait.Iterator.html
+//! [`DoubleEndedIterator`]: https://doc.rust-lang.org/core/iter/trait.DoubleEndedIterator.html
+//! [`ExactSizeIterator`]: https://doc.rust-lang.org/core/iter/trait.ExactSizeIterator.html
+//! [`FusedIterator`]: https://doc.rust-lang.org/core/iter/trait.FusedIterator.html
+//! [`IntoIterator`]: https://doc.rust-lang.org/core/iter/trait.IntoIterator.html
+//! [`FromIterator`]: https://doc.rust-lang.org/core/iter/trait.FromIterator.html
+//! [`Extend`]: https://doc.rust-lang.org/core/iter/trait.Extend.html
+//! [`Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
+//! [`BufRead`]: https://doc.rust-lang.org/std/io/trait.BufRead.html
+//! [`Seek`]: https://doc.rust-lang.org/std/io/trait.Seek.html
+//! [`Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
+//! [`fmt::Write`]: https://doc.rust-lang.org/core/fmt/trait.Write.html
 
 #![recursion_limit = "128"]
 
@@ -211,32 +304,48 @@ mod add_assign_like;
 mod add_helpers;
 #[cfg(feature = "add_like")]
 mod add_like;
+#[cfg(any(feature = "as_ref", feature = "as_mut"))]
+mod as_ref;
+#[cfg(any(feature = "borrow", feature = "borrow_mut"))]
+mod borrow;
+#[cfg(feature = "cmp")]
+mod cmp;
+#[cfg(any(feature = "partial_eq_inner", feature = "partial_ord_inner"))]
+mod cmp_inner;
 #[cfg(feature = "constructor")]
 mod constructor;
-#[cfg(feature = "deref")]
+#[cfg(feature = "default_custom")]
+mod default_custom;
+#[cfg(any(feature = "deref", feature = "deref_mut"))]
 mod deref;
-#[cfg(feature = "deref_mut")]
-mod deref_mut;
-#[cfg(feature = "deref_mut_to_inner")]
-mod deref_mut_to_inner;
-#[cfg(feature = "deref_to_inner")]
-mod deref_to_inner;
 #[cfg(feature = "display")]
 mod display;
+#[cfg(feature = "error")]
+mod error;
 #[cfg(feature = "from")]
 mod from;
+#[cfg(any(feature = "from_iterator", feature = "extend"))]
+mod from_iterator;
 #[cfg(feature = "from_str")]
 mod from_str;
-#[cfg(feature = "index")]
+#[cfg(feature = "getters")]
+mod getters;
+#[cfg(any(feature = "index", feature = "index_mut"))]
 mod index;
-#[cfg(feature = "index_mut")]
-mod index_mut;
 #[cfg(feature = "into")]
 mod into;
 #[cfg(feature = "into_iterator")]
 mod into_iterator;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "is_variant")]
+mod is_variant;
 #[cfg(feature = "iterator")]
 mod iterator;
+#[cfg(any(feature = "iterator", feature = "into_iterator"))]
+mod iterator_helpers;
+#[cfg(feature = "kind")]
+mod kind;
 #[cfg(feature = "mul_assign_like")]
 mod mul_assign_like;
 #[cfg(any(feature = "mul_like", feature = "mul_assign_like"))]
@@ -253,6 +362,12 @@ mod parsing;
 mod sum_like;
 #[cfg(feature = "try_into")]
 mod try_into;
+#[cfg(feature = "unwrap")]
+mod unwrap;
+#[cfg(feature = "variant_structs")]
+mod variant_structs;
+#[cfg(feature = "variants")]
+mod variants;
 
 // This trait describes the possible return types of
 // the derives. A derive can generally be infallible and
@@ -289,99 +404,132 @@ macro_rules! create_derive(
     }
 );
 
-create_derive!("from", from, From, from_derive);
+create_derive!("from", from, From, from_derive, from);
 
-create_derive!("into", into, Into, into_derive);
-create_derive!("into", into, IntoRef, into_ref_derive);
-create_derive!("into", into, IntoRefMut, into_ref_mut_derive);
+create_derive!("into", into, Into, into_derive, into);
+create_derive!("into", into, IntoRef, into_ref_derive, into);
+create_derive!("into", into, IntoRefMut, into_ref_mut_derive, into);
 
 create_derive!("constructor", constructor, Constructor, constructor_derive);
 
-create_derive!("not_like", not_like, Not, not_derive);
-create_derive!("not_like", not_like, Neg, neg_derive);
-
-create_derive!("add_like", add_like, Add, add_derive);
-create_derive!("add_like", add_like, Sub, sub_derive);
-create_derive!("add_like", add_like, BitAnd, bit_and_derive);
-create_derive!("add_like", add_like, BitOr, bit_or_derive);
-create_derive!("add_like", add_like, BitXor, bit_xor_derive);
-create_derive!("add_like", add_like, MulSelf, mul_self_derive);
-create_derive!("add_like", add_like, DivSelf, div_self_derive);
-create_derive!("add_like", add_like, RemSelf, rem_self_derive);
-create_derive!("add_like", add_like, ShrSelf, shr_self_derive);
-create_derive!("add_like", add_like, ShlSelf, shl_self_derive);
-
-create_derive!("mul_like", mul_like, Mul, mul_derive);
-create_derive!("mul_like", mul_like, Div, div_derive);
-create_derive!("mul_like", mul_like, Rem, rem_derive);
-create_derive!("mul_like", mul_like, Shr, shr_derive);
-create_derive!("mul_like", mul_like, Shl, shl_derive);
+create_derive!(
+    "default_custom",
+    default_custom,
+    DefaultCustom,
+    default_custom_derive,
+    default
+);
+
+create_derive!("not_like", not_like, Not, not_derive, not);
+create_derive!("not_like", not_like, Neg, neg_derive, not);
+
+create_derive!("add_like", add_like, Add, add_derive, add);
+create_derive!("add_like", add_like, Sub, sub_derive, add);
+create_derive!("add_like", add_like, BitAnd, bit_and_derive, add);
+create_derive!("add_like", add_like, BitOr, bit_or_derive, add);
+create_derive!("add_like", add_like, BitXor, bit_xor_derive, add);
+create_derive!("add_like", add_like, MulSelf, mul_self_derive, add);
+create_derive!("add_like", add_like, DivSelf, div_self_derive, add);
+create_derive!("add_like", add_like, RemSelf, rem_self_derive, add);
+create_derive!("add_like", add_like, ShrSelf, shr_self_derive, add);
+create_derive!("add_like", add_like, ShlSelf, shl_self_derive, add);
+
+create_derive!("mul_like", mul_like, Mul, mul_derive, mul);
+create_derive!("mul_like", mul_like, Div, div_derive, mul);
+create_derive!("mul_like", mul_like, Rem, rem_derive, mul);
+create_derive!("mul_like", mul_like, Shr, shr_derive, mul);
+create_derive!("mul_like", mul_like, Shl, shl_derive, mul);
 
 create_derive!(
     "add_assign_like",
     add_assign_like,
     AddAssign,
-    add_assign_derive
+    add_assign_derive,
+    add
 );
 create_derive!(
     "add_assign_like",
     add_assign_like,
     SubAssign,
-    sub_assign_derive
+    sub_assign_derive,
+    add
 );
 create_derive!(
     "add_assign_like",
     add_assign_like,
     BitAndAssign,
-    bit_and_assign_derive
+    bit_and_assign_derive,
+    add
 );
 create_derive!(
     "add_assign_like",
     add_assign_like,
     BitOrAssign,
-    bit_or_assign_derive
+    bit_or_assign_derive,
+    add
 );
 create_derive!(
     "add_assign_like",
     add_assign_like,
     BitXorAssign,
-    bit_xor_assign_derive
+    bit_xor_assign_derive,
+    add
 );
 
 create_derive!(
     "mul_assign_like",
     mul_assign_like,
     MulAssign,
-    mul_assign_derive
+    mul_assign_derive,
+    mul
 );
 create_derive!(
     "mul_assign_like",
     mul_assign_like,
     DivAssign,
-    div_assign_derive
+    div_assign_derive,
+    mul
 );
 create_derive!(
     "mul_assign_like",
     mul_assign_like,
     RemAssign,
-    rem_assign_derive
+    rem_assign_derive,
+    mul
 );
 create_derive!(
     "mul_assign_like",
     mul_assign_like,
     ShrAssign,
-    shr_assign_derive
+    shr_assign_derive,
+    mul
 );
 create_derive!(
     "mul_assign_like",
     mul_assign_like,
     ShlAssign,
-    shl_assign_derive
+    shl_assign_derive,
+    mul
 );
 
 create_derive!("sum_like", sum_like, Sum, sum_derive);
 create_derive!("sum_like", sum_like, Product, product_derive);
 
+create_derive!(
+    "from_iterator",
+    from_iterator,
+    FromIterator,
+    from_iterator_derive,
+    from_iterator
+);
+create_derive!(
+    "extend",
+    from_iterator,
+    Extend,
+    extend_derive,
+    from_iterator
+);
+
 create_derive!("from_str", from_str, FromStr, from_str_derive);
 
 create_derive!("display", display, Display, display_derive, display);
@@ -394,44 +542,103 @@ create_derive!("display", display, UpperExp, upper_exp_derive, upper_exp);
 create_derive!("display", display, Pointer, pointer_derive, pointer);
 create_derive!("display", display, DebugCustom, debug_custom_derive, debug);
 
-create_derive!("index", index, Index, index_derive);
-create_derive!("index_mut", index_mut, IndexMut, index_mut_derive);
+create_derive!("error", error, Error, error_derive, error);
+
+create_derive!("getters", getters, Getters, getters_derive, getter);
+
+create_derive!("index", index, Index, index_derive, index);
+create_derive!("index_mut", index, IndexMut, index_mut_derive, index);
 
 create_derive!(
     "into_iterator",
     into_iterator,
     IntoIterator,
-    into_iterator_derive
+    into_iterator_derive,
+    into_iterator
 );
+create_derive!("iterator", iterator, Iterator, iterator_derive, iterator);
 create_derive!(
-    "into_iterator",
-    into_iterator,
-    IntoIteratorRef,
-    into_iterator_ref_derive
+    "iterator",
+    iterator,
+    DoubleEndedIterator,
+    double_ended_iterator_derive,
+    iterator
 );
 create_derive!(
-    "into_iterator",
-    into_iterator,
-    IntoIteratorRefMut,
-    into_iterator_mut_ref_derive
+    "iterator",
+    iterator,
+    ExactSizeIterator,
+    exact_size_iterator_derive,
+    iterator
+);
+create_derive!(
+    "iterator",
+    iterator,
+    FusedIterator,
+    fused_iterator_derive,
+    iterator
+);
+
+create_derive!("std", io, Read, read_derive, io);
+create_derive!("std", io, Write, write_derive, io);
+create_derive!("std", io, BufRead, buf_read_derive, io);
+create_derive!("std", io, Seek, seek_derive, io);
+create_derive!("std", io, FmtWrite, fmt_write_derive, fmt_write);
+
+create_derive!(
+    "is_variant",
+    is_variant,
+    IsVariant,
+    is_variant_derive,
+    is_variant
 );
-create_derive!("iterator", iterator, Iterator, iterator_derive);
+
+create_derive!("kind", kind, Kind, kind_derive, kind);
 
 create_derive!("try_into", try_into, TryInto, try_into_derive);
 create_derive!("try_into", try_into, TryIntoRef, try_into_ref_derive);
 create_derive!("try_into", try_into, TryIntoRefMut, try_into_ref_mut_derive);
 
-create_derive!("deref", deref, Deref, deref_derive);
-create_derive!("deref_mut", deref_mut, DerefMut, deref_mut_derive);
+create_derive!("unwrap", unwrap, Unwrap, unwrap_derive, unwrap);
+create_derive!("unwrap", unwrap, TryUnwrap, try_unwrap_derive, try_unwrap);
+create_derive!("unwrap", unwrap, AsVariant, as_variant_derive, as_variant);
+
+create_derive!("variants", variants, Variants, variants_derive, variants);
+
+create_derive!(
+    "variant_structs",
+    variant_structs,
+    VariantStructs,
+    variant_structs_derive,
+    variant_structs
+);
+
+create_derive!("as_ref", as_ref, AsRef, as_ref_derive, as_ref);
+create_derive!("as_mut", as_ref, AsMut, as_mut_derive, as_mut);
+
+create_derive!("deref", deref, Deref, deref_derive, deref);
+create_derive!("deref_mut", deref, DerefMut, deref_mut_derive, deref);
+
+create_derive!("borrow", borrow, Borrow, borrow_derive, borrow);
+create_derive!("borrow_mut", borrow, BorrowMut, borrow_mut_derive, borrow);
+
+create_derive!("cmp", cmp, PartialEqCustom, partial_eq_custom_derive, cmp);
+create_derive!("cmp", cmp, EqCustom, eq_custom_derive, cmp);
+create_derive!("cmp", cmp, HashCustom, hash_custom_derive, cmp);
+create_derive!("cmp", cmp, PartialOrdCustom, partial_ord_custom_derive, cmp);
+create_derive!("cmp", cmp, OrdCustom, ord_custom_derive, cmp);
+
 create_derive!(
-    "deref_to_inner",
-    deref_to_inner,
-    DerefToInner,
-    deref_to_inner_derive
+    "partial_eq_inner",
+    cmp_inner,
+    PartialEqInner,
+    partial_eq_inner_derive,
+    cmp_inner
 );
 create_derive!(
-    "deref_mut_to_inner",
-    deref_mut_to_inner,
-    DerefMutToInner,
-    deref_mut_to_inner_derive
+    "partial_ord_inner",
+    cmp_inner,
+    PartialOrdInner,
+    partial_ord_inner_derive,
+    cmp_inner
 );
@@ -1,14 +1,15 @@
 use crate::mul_helpers::{struct_exprs, tuple_exprs};
 use crate::utils::{
-    add_where_clauses_for_new_ident, get_field_types_iter, named_to_vec, unnamed_to_vec,
+    add_where_clauses_for_new_ident, get_field_types_iter, get_skipped_fields, named_to_vec,
+    unnamed_to_vec,
 };
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use std::collections::HashSet;
 use std::iter;
-use syn::{Data, DeriveInput, Fields, Ident};
+use syn::{parse::Result, Data, DeriveInput, Fields, Ident};
 
-pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
     let trait_ident = Ident::new(trait_name, Span::call_site());
     let trait_path = &quote!(::core::ops::#trait_ident);
     let method_name = trait_name.to_string();
@@ -18,15 +19,25 @@ pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
     let method_ident = Ident::new(&(method_name.to_string() + "_assign"), Span::call_site());
     let input_type = &input.ident;
 
-    let (exprs, fields) = match input.data {
+    let (exprs, fields, skipped) = match input.data {
         Data::Struct(ref data_struct) => match data_struct.fields {
             Fields::Unnamed(ref fields) => {
                 let field_vec = unnamed_to_vec(fields);
-                (tuple_exprs(&field_vec, &method_ident), field_vec)
+                let skipped = get_skipped_fields(&field_vec, "mul")?;
+                (
+                    tuple_exprs(&field_vec, &skipped, &method_ident),
+                    field_vec,
+                    skipped,
+                )
             }
             Fields::Named(ref fields) => {
                 let field_vec = named_to_vec(fields);
-                (struct_exprs(&field_vec, &method_ident), field_vec)
+                let skipped = get_skipped_fields(&field_vec, "mul")?;
+                (
+                    struct_exprs(&field_vec, &skipped, &method_ident),
+                    field_vec,
+                    skipped,
+                )
             }
             _ => panic!(format!("Unit structs cannot use derive({})", trait_name)),
         },
@@ -34,7 +45,21 @@ pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
         _ => panic!(format!("Only structs can use derive({})", trait_name)),
     };
 
+    // Skipped fields are left as they are
+    let exprs = exprs
+        .into_iter()
+        .zip(&skipped)
+        .filter(|(_, &skip)| !skip)
+        .map(|(expr, _)| expr);
+
     let scalar_ident = &Ident::new("__RhsT", Span::call_site());
+    // Only the fields that are multiplied need a bound
+    let fields: Vec<_> = fields
+        .into_iter()
+        .zip(&skipped)
+        .filter(|(_, &skip)| !skip)
+        .map(|(field, _)| field)
+        .collect();
     let tys = get_field_types_iter(&fields).collect::<HashSet<_>>();
     let tys = tys.iter();
     let scalar_iter = iter::repeat(scalar_ident);
@@ -49,7 +74,7 @@ pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
     let (impl_generics, _, where_clause) = new_generics.split_for_impl();
     let (_, ty_generics, _) = input.generics.split_for_impl();
 
-    quote!(
+    Ok(quote!(
         impl#impl_generics #trait_path<#scalar_ident> for #input_type#ty_generics #where_clause{
             #[inline]
             fn #method_ident(&mut self, rhs: #scalar_ident#ty_generics) {
@@ -57,5 +82,5 @@ pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
                   )*
             }
         }
-    )
+    ))
 }
@@ -3,16 +3,30 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{Field, Ident};
 
-pub fn tuple_exprs(fields: &[&Field], method_ident: &Ident) -> Vec<TokenStream> {
+pub fn tuple_exprs(fields: &[&Field], skipped: &[bool], method_ident: &Ident) -> Vec<TokenStream> {
     number_idents(fields.len())
         .iter()
-        .map(|i| quote!(self.#i.#method_ident(rhs)))
+        .zip(skipped)
+        .map(|(i, &skip)| {
+            if skip {
+                quote!(self.#i)
+            } else {
+                quote!(self.#i.#method_ident(rhs))
+            }
+        })
         .collect()
 }
 
-pub fn struct_exprs(fields: &[&Field], method_ident: &Ident) -> Vec<TokenStream> {
+pub fn struct_exprs(fields: &[&Field], skipped: &[bool], method_ident: &Ident) -> Vec<TokenStream> {
     field_idents(fields)
         .iter()
-        .map(|f| quote!(self.#f.#method_ident(rhs)))
+        .zip(skipped)
+        .map(|(f, &skip)| {
+            if skip {
+                quote!(self.#f)
+            } else {
+                quote!(self.#f.#method_ident(rhs))
+            }
+        })
         .collect()
 }
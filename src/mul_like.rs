@@ -1,35 +1,39 @@
 use crate::mul_helpers::{struct_exprs, tuple_exprs};
 use crate::utils::{
-    add_where_clauses_for_new_ident, field_idents, get_field_types_iter, named_to_vec,
-    unnamed_to_vec,
+    add_where_clauses_for_new_ident, field_idents, get_field_types_iter, get_skipped_fields,
+    named_to_vec, unnamed_to_vec,
 };
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
 use std::collections::HashSet;
 use std::iter;
-use syn::{Data, DeriveInput, Field, Fields, Ident};
+use syn::{parse::Result, Data, DeriveInput, Field, Fields, Ident};
 
-pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
     let trait_ident = Ident::new(trait_name, Span::call_site());
     let trait_path = &quote!(::core::ops::#trait_ident);
     let method_name = trait_name.to_lowercase();
     let method_ident = &Ident::new(&method_name, Span::call_site());
     let input_type = &input.ident;
 
-    let (block, fields) = match input.data {
+    let (block, fields, skipped) = match input.data {
         Data::Struct(ref data_struct) => match data_struct.fields {
             Fields::Unnamed(ref fields) => {
                 let field_vec = unnamed_to_vec(fields);
+                let skipped = get_skipped_fields(&field_vec, "mul")?;
                 (
-                    tuple_content(input_type, &field_vec, method_ident),
+                    tuple_content(input_type, &field_vec, &skipped, method_ident),
                     field_vec,
+                    skipped,
                 )
             }
             Fields::Named(ref fields) => {
                 let field_vec = named_to_vec(fields);
+                let skipped = get_skipped_fields(&field_vec, "mul")?;
                 (
-                    struct_content(input_type, &field_vec, method_ident),
+                    struct_content(input_type, &field_vec, &skipped, method_ident),
                     field_vec,
+                    skipped,
                 )
             }
             _ => panic!(format!("Unit structs cannot use derive({})", trait_name)),
@@ -38,6 +42,13 @@ pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
     };
 
     let scalar_ident = &Ident::new("__RhsT", Span::call_site());
+    // Only the fields that are multiplied need a bound
+    let fields: Vec<_> = fields
+        .into_iter()
+        .zip(&skipped)
+        .filter(|(_, &skip)| !skip)
+        .map(|(field, _)| field)
+        .collect();
     let tys = get_field_types_iter(&fields).collect::<HashSet<_>>();
     let tys = tys.iter();
     let scalar_iter = iter::repeat(scalar_ident);
@@ -52,7 +63,7 @@ pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
     let (impl_generics, _, where_clause) = new_generics.split_for_impl();
     let (_, ty_generics, _) = input.generics.split_for_impl();
 
-    quote!(
+    Ok(quote!(
         impl#impl_generics  #trait_path<#scalar_ident> for #input_type#ty_generics #where_clause {
             type Output = #input_type#ty_generics;
             #[inline]
@@ -60,25 +71,26 @@ pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
                 #block
             }
         }
-
-    )
+    ))
 }
 
 fn tuple_content<'a, T: ToTokens>(
     input_type: &T,
     fields: &[&'a Field],
+    skipped: &[bool],
     method_ident: &Ident,
 ) -> TokenStream {
-    let exprs = tuple_exprs(fields, method_ident);
+    let exprs = tuple_exprs(fields, skipped, method_ident);
     quote!(#input_type(#(#exprs),*))
 }
 
 fn struct_content<'a, T: ToTokens>(
     input_type: &T,
     fields: &[&'a Field],
+    skipped: &[bool],
     method_ident: &Ident,
 ) -> TokenStream {
-    let exprs = struct_exprs(fields, method_ident);
+    let exprs = struct_exprs(fields, skipped, method_ident);
     let field_names = field_idents(fields);
     quote!(#input_type{#(#field_names: #exprs),*})
 }
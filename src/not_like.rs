@@ -1,31 +1,41 @@
-use crate::utils::{add_extra_type_param_bound_op_output, named_to_vec, unnamed_to_vec};
+use crate::utils::{
+    add_extra_type_param_bound_op_output, get_skipped_fields, get_skipped_type_params,
+    named_to_vec, unnamed_to_vec,
+};
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
-use std::iter;
-use syn::{Data, DataEnum, DeriveInput, Field, Fields, Ident, Index};
+use syn::{parse::Result, Data, DataEnum, DeriveInput, Field, Fields, Ident, Index};
 
-pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
     let trait_ident = Ident::new(trait_name, Span::call_site());
     let method_name = trait_name.to_lowercase();
     let method_ident = &Ident::new(&method_name, Span::call_site());
     let input_type = &input.ident;
 
-    let generics = add_extra_type_param_bound_op_output(&input.generics, &trait_ident);
+    let fields: Vec<&Field> = match input.data {
+        Data::Struct(ref data_struct) => data_struct.fields.iter().collect(),
+        Data::Enum(ref data_enum) => data_enum.variants.iter().flat_map(|v| &v.fields).collect(),
+        _ => vec![],
+    };
+    let skipped = get_skipped_fields(&fields, "not")?;
+    let skipped_params = get_skipped_type_params(&input.generics, &fields, &skipped);
+    let generics =
+        add_extra_type_param_bound_op_output(&input.generics, &trait_ident, &skipped_params);
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let (output_type, block) = match input.data {
         Data::Struct(ref data_struct) => match data_struct.fields {
-            Fields::Unnamed(ref fields) => (
+            Fields::Unnamed(_) => (
                 quote!(#input_type#ty_generics),
-                tuple_content(input_type, &unnamed_to_vec(fields), method_ident),
+                tuple_content(input_type, &fields, &skipped, method_ident),
             ),
-            Fields::Named(ref fields) => (
+            Fields::Named(_) => (
                 quote!(#input_type#ty_generics),
-                struct_content(input_type, &named_to_vec(fields), method_ident),
+                struct_content(input_type, &fields, &skipped, method_ident),
             ),
             _ => panic!(format!("Unit structs cannot use derive({})", trait_name)),
         },
-        Data::Enum(ref data_enum) => enum_output_type_and_content(input, data_enum, method_ident),
+        Data::Enum(ref data_enum) => enum_output_type_and_content(input, data_enum, method_ident)?,
 
         _ => panic!(format!(
             "Only structs and enums can use derive({})",
@@ -33,7 +43,7 @@ pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
         )),
     };
 
-    quote!(
+    Ok(quote!(
         impl#impl_generics ::core::ops::#trait_ident for #input_type#ty_generics #where_clause {
             type Output = #output_type;
             #[inline]
@@ -41,34 +51,48 @@ pub fn expand(input: &DeriveInput, trait_name: &str) -> TokenStream {
                 #block
             }
         }
-    )
+    ))
 }
 
 fn tuple_content<T: ToTokens>(
     input_type: &T,
     fields: &[&Field],
+    skipped: &[bool],
     method_ident: &Ident,
 ) -> TokenStream {
     let mut exprs = vec![];
 
-    for i in 0..fields.len() {
+    for (i, (_, &skip)) in fields.iter().zip(skipped).enumerate() {
         let i = Index::from(i);
-        // generates `self.0.add()`
-        let expr = quote!(self.#i.#method_ident());
+        // generates `self.0.not()`, or `self.0` for skipped fields
+        let expr = if skip {
+            quote!(self.#i)
+        } else {
+            quote!(self.#i.#method_ident())
+        };
         exprs.push(expr);
     }
 
     quote!(#input_type(#(#exprs),*))
 }
 
-fn struct_content(input_type: &Ident, fields: &[&Field], method_ident: &Ident) -> TokenStream {
+fn struct_content(
+    input_type: &Ident,
+    fields: &[&Field],
+    skipped: &[bool],
+    method_ident: &Ident,
+) -> TokenStream {
     let mut exprs = vec![];
 
-    for field in fields {
+    for (field, &skip) in fields.iter().zip(skipped) {
         // It's safe to unwrap because struct fields always have an identifier
         let field_id = field.ident.as_ref();
-        // generates `x: self.x.not()`
-        let expr = quote!(#field_id: self.#field_id.#method_ident());
+        // generates `x: self.x.not()`, or `x: self.x` for skipped fields
+        let expr = if skip {
+            quote!(#field_id: self.#field_id)
+        } else {
+            quote!(#field_id: self.#field_id.#method_ident())
+        };
         exprs.push(expr)
     }
 
@@ -79,17 +103,18 @@ fn enum_output_type_and_content(
     input: &DeriveInput,
     data_enum: &DataEnum,
     method_ident: &Ident,
-) -> (TokenStream, TokenStream) {
+) -> Result<(TokenStream, TokenStream)> {
     let input_type = &input.ident;
     let (_, ty_generics, _) = input.generics.split_for_impl();
     let mut matches = vec![];
-    let mut method_iter = iter::repeat(method_ident);
     // If the enum contains unit types that means it can error.
     let has_unit_type = data_enum.variants.iter().any(|v| v.fields == Fields::Unit);
 
     for variant in &data_enum.variants {
         let subtype = &variant.ident;
         let subtype = quote!(#input_type::#subtype);
+        let field_vec: Vec<_> = variant.fields.iter().collect();
+        let skipped = get_skipped_fields(&field_vec, "not")?;
 
         match variant.fields {
             Fields::Unnamed(ref fields) => {
@@ -99,8 +124,8 @@ fn enum_output_type_and_content(
                 let vars: &Vec<_> = &(0..size)
                     .map(|i| Ident::new(&format!("__{}", i), Span::call_site()))
                     .collect();
-                let method_iter = method_iter.by_ref();
-                let mut body = quote!(#subtype(#(#vars.#method_iter()),*));
+                let exprs = variant_exprs(vars, &skipped, method_ident);
+                let mut body = quote!(#subtype(#(#exprs),*));
                 if has_unit_type {
                     body = quote!(::core::result::Result::Ok(#body))
                 }
@@ -125,8 +150,8 @@ fn enum_output_type_and_content(
                 let vars: &Vec<_> = &(0..size)
                     .map(|i| Ident::new(&format!("__{}", i), Span::call_site()))
                     .collect();
-                let method_iter = method_iter.by_ref();
-                let mut body = quote!(#subtype{#(#field_names: #vars.#method_iter()),*});
+                let exprs = variant_exprs(vars, &skipped, method_ident);
+                let mut body = quote!(#subtype{#(#field_names: #exprs),*});
                 if has_unit_type {
                     body = quote!(::core::result::Result::Ok(#body))
                 }
@@ -156,5 +181,20 @@ fn enum_output_type_and_content(
         quote!(#input_type#ty_generics)
     };
 
-    (output_type, body)
+    Ok((output_type, body))
+}
+
+/// Returns the expressions that apply the operation to the fields of a variant, which are bound
+/// to `vars`. Skipped fields are kept as they are.
+fn variant_exprs(vars: &[Ident], skipped: &[bool], method_ident: &Ident) -> Vec<TokenStream> {
+    vars.iter()
+        .zip(skipped)
+        .map(|(var, &skip)| {
+            if skip {
+                quote!(#var)
+            } else {
+                quote!(#var.#method_ident())
+            }
+        })
+        .collect()
 }
@@ -0,0 +1,177 @@
+use crate::utils::{get_matcher, variant_method_name};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse::{Error, Result},
+    spanned::Spanned,
+    Data, DataEnum, DeriveInput, Fields, Ident, Type, Variant,
+};
+
+/// Provides the hook to expand `#[derive(Unwrap)]`, `#[derive(TryUnwrap)]` and
+/// `#[derive(AsVariant)]` into the corresponding methods for every variant
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
+    let data_enum = match input.data {
+        Data::Enum(ref data_enum) => data_enum,
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                format!("Only enums can derive({})", trait_name),
+            ))
+        }
+    };
+    let attr_name = match trait_name {
+        "Unwrap" => "unwrap",
+        "TryUnwrap" => "try_unwrap",
+        "AsVariant" => "as_variant",
+        _ => unreachable!(),
+    };
+    let enum_name = &input.ident;
+
+    let mut methods = vec![];
+    for variant in &data_enum.variants {
+        let name = match variant_method_name(variant, attr_name)? {
+            Some(name) => name,
+            None => continue,
+        };
+        let variant_ident = &variant.ident;
+        let matcher = get_matcher(&variant.fields);
+        let pattern = quote!(#enum_name::#variant_ident #matcher);
+        let bindings = bindings(&variant.fields);
+        let types = field_types(&variant.fields);
+        let (value, owned_type, ref_type, mut_type) = if bindings.len() == 1 {
+            let ty = types[0];
+            (
+                quote!(#(#bindings)*),
+                quote!(#ty),
+                quote!(&#ty),
+                quote!(&mut #ty),
+            )
+        } else {
+            (
+                quote!((#(#bindings),*)),
+                quote!((#(#types),*)),
+                quote!((#(&#types),*)),
+                quote!((#(&mut #types),*)),
+            )
+        };
+
+        match trait_name {
+            "Unwrap" => {
+                let fn_name = Ident::new(&format!("unwrap_{}", name), variant_ident.span());
+                let doc = format!(
+                    "Returns the fields of the `{}::{}` variant.\n\n\
+                     # Panics\n\n\
+                     Panics if this value is of any other variant.",
+                    enum_name, variant_ident
+                );
+                let other_arms = other_variants(data_enum, variant).map(|other| {
+                    let other_ident = &other.ident;
+                    let message = format!(
+                        "called `{}::{}()` on a `{}::{}` value",
+                        enum_name, fn_name, enum_name, other_ident
+                    );
+                    quote!(#enum_name::#other_ident { .. } => panic!(#message))
+                });
+                methods.push(quote! {
+                    #[doc = #doc]
+                    #[inline]
+                    #[track_caller]
+                    pub fn #fn_name(self) -> #owned_type {
+                        match self {
+                            #pattern => #value,
+                            #(#other_arms),*
+                        }
+                    }
+                });
+            }
+            "TryUnwrap" => {
+                let fn_name = Ident::new(&format!("try_unwrap_{}", name), variant_ident.span());
+                let doc = format!(
+                    "Returns the fields of the `{}::{}` variant, or `self` if it's of any other \
+                     variant.",
+                    enum_name, variant_ident
+                );
+                methods.push(quote! {
+                    #[doc = #doc]
+                    #[inline]
+                    #[allow(unreachable_patterns)]
+                    pub fn #fn_name(self) -> ::core::result::Result<#owned_type, Self> {
+                        match self {
+                            #pattern => ::core::result::Result::Ok(#value),
+                            other => ::core::result::Result::Err(other),
+                        }
+                    }
+                });
+            }
+            "AsVariant" => {
+                let fn_name = Ident::new(&format!("as_{}", name), variant_ident.span());
+                let fn_name_mut = Ident::new(&format!("as_{}_mut", name), variant_ident.span());
+                let doc = format!(
+                    "Returns references to the fields of the `{}::{}` variant, or `None` if \
+                     this value is of any other variant.",
+                    enum_name, variant_ident
+                );
+                let doc_mut = format!(
+                    "Returns mutable references to the fields of the `{}::{}` variant, or \
+                     `None` if this value is of any other variant.",
+                    enum_name, variant_ident
+                );
+                methods.push(quote! {
+                    #[doc = #doc]
+                    #[inline]
+                    #[allow(unreachable_patterns)]
+                    pub fn #fn_name(&self) -> ::core::option::Option<#ref_type> {
+                        match self {
+                            #pattern => ::core::option::Option::Some(#value),
+                            _ => ::core::option::Option::None,
+                        }
+                    }
+
+                    #[doc = #doc_mut]
+                    #[inline]
+                    #[allow(unreachable_patterns)]
+                    pub fn #fn_name_mut(&mut self) -> ::core::option::Option<#mut_type> {
+                        match self {
+                            #pattern => ::core::option::Option::Some(#value),
+                            _ => ::core::option::Option::None,
+                        }
+                    }
+                });
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl#impl_generics #enum_name#ty_generics #where_clause {
+            #(#methods)*
+        }
+    })
+}
+
+/// The names that the fields are bound to by `get_matcher`.
+fn bindings(fields: &Fields) -> Vec<Ident> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| match f.ident {
+            Some(ref ident) => ident.clone(),
+            None => Ident::new(&format!("_{}", i), Span::call_site()),
+        })
+        .collect()
+}
+
+fn field_types(fields: &Fields) -> Vec<&Type> {
+    fields.iter().map(|f| &f.ty).collect()
+}
+
+fn other_variants<'a>(
+    data_enum: &'a DataEnum,
+    variant: &'a Variant,
+) -> impl Iterator<Item = &'a Variant> + 'a {
+    data_enum
+        .variants
+        .iter()
+        .filter(move |v| v.ident != variant.ident)
+}
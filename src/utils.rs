@@ -1,10 +1,14 @@
 #![allow(dead_code)]
 
-use proc_macro2::{Span, TokenStream};
-use quote::quote;
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
 use syn::{
-    parse_str, Field, FieldsNamed, FieldsUnnamed, GenericParam, Generics, Ident, Index, Type,
-    TypeParamBound, WhereClause,
+    parse::{Error, Result},
+    parse_quote, parse_str,
+    spanned::Spanned,
+    Attribute, Data, DataEnum, DeriveInput, Field, Fields, FieldsNamed, FieldsUnnamed,
+    GenericParam, Generics, Ident, Index, Lifetime, Lit, LitStr, Member, Meta, NestedMeta, Path,
+    Type, TypeParamBound, Variant, WhereClause,
 };
 
 #[derive(Clone, Copy)]
@@ -55,7 +59,6 @@ impl RefType {
         }
     }
 
-
     pub fn reference_with_lifetime(self) -> TokenStream {
         if !self.is_ref() {
             return quote!();
@@ -107,9 +110,13 @@ pub fn get_field_types<'a>(fields: &'a [&'a Field]) -> Vec<&'a Type> {
 pub fn add_extra_type_param_bound_op_output<'a>(
     generics: &'a Generics,
     trait_ident: &'a Ident,
+    skipped_params: &[Ident],
 ) -> Generics {
     let mut generics = generics.clone();
     for type_param in &mut generics.type_params_mut() {
+        if skipped_params.contains(&type_param.ident) {
+            continue;
+        }
         let type_ident = &type_param.ident;
         let bound: TypeParamBound =
             parse_str(&quote!(::core::ops::#trait_ident<Output=#type_ident>).to_string()).unwrap();
@@ -119,8 +126,20 @@ pub fn add_extra_type_param_bound_op_output<'a>(
     generics
 }
 
-pub fn add_extra_ty_param_bound_op<'a>(generics: &'a Generics, trait_ident: &'a Ident) -> Generics {
-    add_extra_ty_param_bound(generics, &quote!(::core::ops::#trait_ident))
+pub fn add_extra_ty_param_bound_op<'a>(
+    generics: &'a Generics,
+    trait_ident: &'a Ident,
+    skipped_params: &[Ident],
+) -> Generics {
+    let mut generics = generics.clone();
+    let bound: TypeParamBound = parse_quote!(::core::ops::#trait_ident);
+    for type_param in &mut generics.type_params_mut() {
+        if !skipped_params.contains(&type_param.ident) {
+            type_param.bounds.push(bound.clone())
+        }
+    }
+
+    generics
 }
 
 pub fn add_extra_ty_param_bound<'a>(generics: &'a Generics, bound: &'a TokenStream) -> Generics {
@@ -189,6 +208,42 @@ pub fn add_where_clauses_for_new_ident<'a>(
     add_extra_generic_param(&generics, generic_param)
 }
 
+/// Returns how the `index`-th field of a struct or variant is accessed, e.g. `self.#member`.
+pub fn field_member(index: usize, field: &Field) -> Member {
+    match field.ident {
+        Some(ref ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(Index::from(index)),
+    }
+}
+
+/// Returns the pattern that binds all fields of a struct or variant by name, i.e. `_0`, `_1`, ...
+/// for tuple fields and the field names for named fields. The path itself is not included.
+pub fn get_matcher(fields: &Fields) -> TokenStream {
+    match fields {
+        Fields::Unit => TokenStream::new(),
+        Fields::Unnamed(fields) => {
+            let fields: TokenStream = (0..fields.unnamed.len())
+                .map(|n| {
+                    let i = Ident::new(&format!("_{}", n), Span::call_site());
+                    quote!(#i,)
+                })
+                .collect();
+            quote!((#fields))
+        }
+        Fields::Named(fields) => {
+            let fields: TokenStream = fields
+                .named
+                .iter()
+                .map(|f| {
+                    let i = f.ident.as_ref().unwrap();
+                    quote!(#i,)
+                })
+                .collect();
+            quote!({#fields})
+        }
+    }
+}
+
 pub fn unnamed_to_vec(fields: &FieldsUnnamed) -> Vec<&Field> {
     fields.unnamed.iter().collect()
 }
@@ -196,3 +251,546 @@ pub fn unnamed_to_vec(fields: &FieldsUnnamed) -> Vec<&Field> {
 pub fn named_to_vec(fields: &FieldsNamed) -> Vec<&Field> {
     fields.named.iter().collect()
 }
+
+/// Collects the arguments of all `#[attr_name(...)]` attributes in `attrs`.
+/// Returns `None` if there is no such attribute, while a bare `#[attr_name]` results in an empty
+/// list, so it can be used as a marker.
+pub fn get_attr_args(attrs: &[Attribute], attr_name: &str) -> Result<Option<Vec<NestedMeta>>> {
+    let mut args: Option<Vec<NestedMeta>> = None;
+    for attr in attrs.iter().filter(|a| a.path.is_ident(attr_name)) {
+        let nested = match attr.parse_meta()? {
+            Meta::Path(_) => vec![],
+            Meta::List(list) => list.nested.into_iter().collect(),
+            meta @ Meta::NameValue(_) => {
+                return Err(Error::new(
+                    meta.span(),
+                    format!("Expected #[{}] or #[{}(...)]", attr_name, attr_name),
+                ))
+            }
+        };
+        args.get_or_insert_with(Vec::new).extend(nested);
+    }
+    Ok(args)
+}
+
+/// Checks if `arg` is a plain identifier that's equal to `flag`, e.g. `skip` in `#[debug(skip)]`.
+pub fn is_flag(arg: &NestedMeta, flag: &str) -> bool {
+    match arg {
+        NestedMeta::Meta(Meta::Path(path)) => path.is_ident(flag),
+        _ => false,
+    }
+}
+
+/// Returns for every field whether it's marked with `#[attr_name(skip)]`, which operator derives
+/// like `Add` use to leave a field out of the operation.
+pub fn get_skipped_fields(fields: &[&Field], attr_name: &str) -> Result<Vec<bool>> {
+    fields
+        .iter()
+        .map(|field| match get_attr_args(&field.attrs, attr_name)? {
+            None => Ok(false),
+            Some(ref args) if args.len() == 1 && is_flag(&args[0], "skip") => Ok(true),
+            Some(_) => Err(Error::new(
+                field.span(),
+                format!("Proper syntax: #[{}(skip)]", attr_name),
+            )),
+        })
+        .collect()
+}
+
+/// Returns the type parameters that are only used in the types of skipped fields, like `Unit` in
+/// a skipped `PhantomData<Unit>` field. Operator derives don't bound these by their trait.
+pub fn get_skipped_type_params(
+    generics: &Generics,
+    fields: &[&Field],
+    skipped: &[bool],
+) -> Vec<Ident> {
+    let used_in = |ident: &Ident, skip: bool| {
+        fields
+            .iter()
+            .zip(skipped)
+            .filter(|(_, &s)| s == skip)
+            .any(|(field, _)| uses_ident(field.ty.to_token_stream(), ident))
+    };
+    generics
+        .type_params()
+        .map(|param| &param.ident)
+        .filter(|ident| used_in(ident, true) && !used_in(ident, false))
+        .cloned()
+        .collect()
+}
+
+/// Returns whether `ident` occurs anywhere in `tokens`.
+pub fn uses_ident(tokens: TokenStream, ident: &Ident) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ref token_ident) => token_ident == ident,
+        TokenTree::Group(ref group) => uses_ident(group.stream(), ident),
+        _ => false,
+    })
+}
+
+/// Returns whether `lifetime` occurs anywhere in `tokens`.
+pub fn uses_lifetime(tokens: TokenStream, lifetime: &Lifetime) -> bool {
+    let mut after_apostrophe = false;
+    tokens.into_iter().any(|token| {
+        let found = match token {
+            TokenTree::Ident(ref ident) => after_apostrophe && *ident == lifetime.ident,
+            TokenTree::Group(ref group) => uses_lifetime(group.stream(), lifetime),
+            _ => false,
+        };
+        after_apostrophe = match token {
+            TokenTree::Punct(ref punct) => punct.as_char() == '\'',
+            _ => false,
+        };
+        found
+    })
+}
+
+/// Returns whether the generic parameter `param` is used anywhere in `tokens`.
+pub fn uses_generic_param(tokens: TokenStream, param: &GenericParam) -> bool {
+    match param {
+        GenericParam::Type(param) => uses_ident(tokens, &param.ident),
+        GenericParam::Lifetime(param) => uses_lifetime(tokens, &param.lifetime),
+        GenericParam::Const(param) => uses_ident(tokens, &param.ident),
+    }
+}
+
+/// Returns the field that a derive like `Index` or `Deref` forwards to, together with the
+/// arguments of its `#[attr_name(...)]` attribute. This is the field marked with `#[attr_name]`,
+/// or the only field if none of them is marked.
+pub fn get_forwarded_field<'a>(
+    fields: &'a Fields,
+    span: Span,
+    trait_name: &str,
+    attr_name: &str,
+) -> Result<(Member, &'a Field, Vec<NestedMeta>)> {
+    let mut marked = None;
+    for (i, field) in fields.iter().enumerate() {
+        if let Some(args) = get_attr_args(&field.attrs, attr_name)? {
+            if marked.is_some() {
+                return Err(Error::new(
+                    field.span(),
+                    format!("Only a single field can be marked with #[{}]", attr_name),
+                ));
+            }
+            marked = Some((field_member(i, field), field, args));
+        }
+    }
+    if let Some(marked) = marked {
+        return Ok(marked);
+    }
+    let mut it = fields.iter();
+    match (it.next(), it.next()) {
+        (Some(field), None) => Ok((field_member(0, field), field, vec![])),
+        _ => Err(Error::new(
+            span,
+            format!(
+                "#[derive({})] requires a single field or a field marked with #[{}]",
+                trait_name, attr_name
+            ),
+        )),
+    }
+}
+
+/// A variant together with the member, and the arguments of the attribute, of its forwarded field.
+pub type VariantField<'a> = (&'a Variant, Member, Vec<NestedMeta>);
+
+/// Returns the forwarded field of every variant of an enum, selected like `get_forwarded_field`
+/// does for structs, together with their type. All of these fields need to have the same type.
+pub fn get_variant_forwarded_fields<'a>(
+    data_enum: &'a DataEnum,
+    span: Span,
+    trait_name: &str,
+    attr_name: &str,
+) -> Result<(&'a Type, Vec<VariantField<'a>>)> {
+    let mut field_type: Option<&Type> = None;
+    let mut forwarded = vec![];
+    for variant in &data_enum.variants {
+        let (member, field, args) =
+            get_forwarded_field(&variant.fields, variant.span(), trait_name, attr_name)?;
+        let ty = &field.ty;
+        match field_type {
+            Some(field_type) if quote!(#ty).to_string() != quote!(#field_type).to_string() => {
+                return Err(Error::new(
+                    ty.span(),
+                    format!(
+                        "#[derive({})] requires the fields of all variants to have the same type",
+                        trait_name
+                    ),
+                ))
+            }
+            Some(_) => (),
+            None => field_type = Some(ty),
+        }
+        forwarded.push((variant, member, args));
+    }
+    match field_type {
+        Some(field_type) => Ok((field_type, forwarded)),
+        None => Err(Error::new(
+            span,
+            format!("#[derive({})] requires at least one variant", trait_name),
+        )),
+    }
+}
+
+/// Generates a `match` on `self` that evaluates to a reference to the given field of every
+/// variant. Whether it is a mutable reference depends on how `self` is borrowed.
+pub fn variant_field_match(enum_ident: &Ident, forwarded: &[VariantField]) -> TokenStream {
+    let arms = forwarded.iter().map(|(variant, member, _)| {
+        let variant_ident = &variant.ident;
+        quote!(#enum_ident::#variant_ident { #member: __deriveMoreField, .. } => __deriveMoreField)
+    });
+    quote! {
+        match self {
+            #(#arms),*
+        }
+    }
+}
+
+/// The variant of an enum, or `None` for a struct, together with the member, the field and the
+/// arguments of the attribute of a field that is forwarded to.
+pub type ForwardedField<'a> = (Option<&'a Ident>, Member, &'a Field, Vec<NestedMeta>);
+
+/// Returns the forwarded field of a struct, or of every variant of an enum, selected like
+/// `get_forwarded_field` does. Unlike with `get_variant_forwarded_fields`, the fields of the
+/// variants can have different types.
+pub fn get_forwarded_fields<'a>(
+    input: &'a DeriveInput,
+    trait_name: &str,
+    attr_name: &str,
+) -> Result<Vec<ForwardedField<'a>>> {
+    match input.data {
+        Data::Struct(ref data_struct) => {
+            let (member, field, args) =
+                get_forwarded_field(&data_struct.fields, input.span(), trait_name, attr_name)?;
+            Ok(vec![(None, member, field, args)])
+        }
+        Data::Enum(ref data_enum) if !data_enum.variants.is_empty() => data_enum
+            .variants
+            .iter()
+            .map(|variant| {
+                let (member, field, args) =
+                    get_forwarded_field(&variant.fields, variant.span(), trait_name, attr_name)?;
+                Ok((Some(&variant.ident), member, field, args))
+            })
+            .collect(),
+        Data::Enum(_) => Err(Error::new(
+            input.span(),
+            format!("#[derive({})] requires at least one variant", trait_name),
+        )),
+        Data::Union(_) => Err(Error::new(
+            input.span(),
+            format!("Only structs and enums can derive({})", trait_name),
+        )),
+    }
+}
+
+/// The variant of an enum, or `None` for a struct, together with the member and the type of a
+/// field that is forwarded to.
+pub type ForwardedType<'a> = (Option<&'a Ident>, Member, &'a Type);
+
+/// Generates an expression that calls `call` with the type of the forwarded field and the field
+/// borrowed as `reference`, which is empty to move it. For enums this is a `match` over the
+/// variants, and `self` needs to be borrowed in the same way as `reference`.
+pub fn forward_call<F>(
+    input_type: &Ident,
+    fields: &[ForwardedType],
+    reference: &TokenStream,
+    call: F,
+) -> TokenStream
+where
+    F: Fn(&Type, TokenStream) -> TokenStream,
+{
+    match fields {
+        [(None, member, ty)] => call(ty, quote!(#reference self.#member)),
+        _ => {
+            let arms = fields.iter().map(|(variant, member, ty)| {
+                let call = call(ty, quote!(__deriveMoreField));
+                quote!(#input_type::#variant { #member: __deriveMoreField, .. } => #call)
+            });
+            quote! {
+                match self {
+                    #(#arms),*
+                }
+            }
+        }
+    }
+}
+
+/// Returns the type of the fields, if there is at least one field and all of them have the same
+/// type. Otherwise an error mentioning `what` requires this is returned.
+pub fn get_common_field_type<'a>(fields: &'a Fields, span: Span, what: &str) -> Result<&'a Type> {
+    let field_type = match fields.iter().next() {
+        Some(field) => &field.ty,
+        None => {
+            return Err(Error::new(
+                span,
+                format!("{} requires at least one field", what),
+            ))
+        }
+    };
+    let type_string = quote!(#field_type).to_string();
+    for field in fields {
+        let ty = &field.ty;
+        if quote!(#ty).to_string() != type_string {
+            return Err(Error::new(
+                ty.span(),
+                format!("{} requires all fields to have the same type", what),
+            ));
+        }
+    }
+    Ok(field_type)
+}
+
+/// Adds a bound on `trait_path` for each of the types that contains a type parameter anywhere,
+/// like `T`, `T::Item` or `Vec<T>`, so the bounds follow the field types instead of requiring
+/// every type parameter to implement the trait.
+pub fn add_inferred_bounds<'a, I>(
+    generics: &Generics,
+    types: I,
+    trait_path: &TokenStream,
+) -> Generics
+where
+    I: IntoIterator<Item = &'a Type>,
+{
+    let type_params: Vec<_> = generics.type_params().map(|param| &param.ident).collect();
+    let mut seen = vec![];
+    let mut bounds = vec![];
+    for ty in types {
+        let has_type_param = type_params
+            .iter()
+            .any(|param| uses_ident(quote!(#ty), param));
+        let ty_string = quote!(#ty).to_string();
+        if has_type_param && !seen.contains(&ty_string) {
+            bounds.push(quote!(#ty: #trait_path));
+            seen.push(ty_string);
+        }
+    }
+    if bounds.is_empty() {
+        return generics.clone();
+    }
+    add_extra_where_clauses(generics, quote!(where #(#bounds),*))
+}
+
+/// Returns whether the type has `#[repr(C)]` or `#[repr(transparent)]`, so its fields are laid
+/// out in declaration order. Packed types are never accepted, as their fields can be misaligned.
+pub fn has_ordered_repr(attrs: &[Attribute]) -> bool {
+    let mut ordered = false;
+    for meta in attrs
+        .iter()
+        .filter(|a| a.path.is_ident("repr"))
+        .filter_map(|a| a.parse_meta().ok())
+    {
+        if let Meta::List(list) = meta {
+            for arg in &list.nested {
+                match arg {
+                    NestedMeta::Meta(meta) if meta.path().is_ident("packed") => return false,
+                    _ if is_flag(arg, "C") || is_flag(arg, "transparent") => ordered = true,
+                    _ => {}
+                }
+            }
+        }
+    }
+    ordered
+}
+
+/// Returns the string value of `arg` if it is `name = "..."`.
+pub fn get_str_arg(arg: &NestedMeta, name: &str) -> Result<Option<LitStr>> {
+    match arg {
+        NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident(name) => match nv.lit {
+            Lit::Str(ref s) => Ok(Some(s.clone())),
+            ref lit => Err(Error::new(
+                lit.span(),
+                format!("Expected a string literal for `{}`", name),
+            )),
+        },
+        _ => Ok(None),
+    }
+}
+
+/// Returns the traits in `arg` if it is `derive(...)`.
+pub fn get_derive_arg(arg: &NestedMeta) -> Result<Option<Vec<Path>>> {
+    match arg {
+        NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("derive") => list
+            .nested
+            .iter()
+            .map(|nested| match nested {
+                NestedMeta::Meta(Meta::Path(path)) => Ok(path.clone()),
+                _ => Err(Error::new(nested.span(), "Expected a trait to derive")),
+            })
+            .collect::<Result<_>>()
+            .map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// Returns the types in `arg` if it is `types(...)`. Types that aren't paths, like `&str`, can be
+/// given as string literals.
+pub fn get_types_arg(arg: &NestedMeta) -> Result<Option<Vec<Type>>> {
+    match arg {
+        NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("types") => list
+            .nested
+            .iter()
+            .map(|nested| match nested {
+                NestedMeta::Meta(Meta::Path(path)) => Ok(parse_quote!(#path)),
+                NestedMeta::Lit(Lit::Str(s)) => s.parse(),
+                _ => Err(Error::new(nested.span(), "Expected a type")),
+            })
+            .collect::<Result<_>>()
+            .map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// Name of an identifier as it should be printed, i.e. without the `r#` prefix.
+pub fn unraw(ident: &Ident) -> LitStr {
+    LitStr::new(ident.to_string().trim_start_matches("r#"), ident.span())
+}
+
+/// Splits an identifier like `HttpError`, `HTTPError` or `http_error` into its words.
+fn split_words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.trim_start_matches("r#").chars().collect();
+    let mut words = vec![];
+    let mut word = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' || c == '-' {
+            if !word.is_empty() {
+                words.push(word);
+                word = String::new();
+            }
+            continue;
+        }
+        if c.is_uppercase() {
+            let prev = if i > 0 { Some(chars[i - 1]) } else { None };
+            let boundary = match (prev, chars.get(i + 1)) {
+                (Some(p), _) if p.is_lowercase() || p.is_numeric() => true,
+                (Some(p), Some(n)) => p.is_uppercase() && n.is_lowercase(),
+                _ => false,
+            };
+            if boundary && !word.is_empty() {
+                words.push(word);
+                word = String::new();
+            }
+        }
+        word.push(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// The case conversions that can be used for `rename_all` attributes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    pub fn parse(lit: &LitStr) -> Result<Self> {
+        Ok(match lit.value().as_str() {
+            "lowercase" => RenameRule::Lower,
+            "UPPERCASE" => RenameRule::Upper,
+            "PascalCase" => RenameRule::Pascal,
+            "camelCase" => RenameRule::Camel,
+            "snake_case" => RenameRule::Snake,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+            "kebab-case" => RenameRule::Kebab,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebab,
+            _ => {
+                return Err(Error::new(
+                    lit.span(),
+                    "Expected one of \"lowercase\", \"UPPERCASE\", \"PascalCase\", \"camelCase\", \
+                     \"snake_case\", \"SCREAMING_SNAKE_CASE\", \"kebab-case\" or \
+                     \"SCREAMING-KEBAB-CASE\"",
+                ))
+            }
+        })
+    }
+
+    pub fn apply(self, ident: &Ident) -> String {
+        let words = split_words(&ident.to_string());
+        let capitalize = |word: &String| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect(),
+                None => String::new(),
+            }
+        };
+        let join = |sep: &str, upper: bool| {
+            let words: Vec<String> = words
+                .iter()
+                .map(|w| {
+                    if upper {
+                        w.to_uppercase()
+                    } else {
+                        w.to_lowercase()
+                    }
+                })
+                .collect();
+            words.join(sep)
+        };
+        match self {
+            RenameRule::Lower => join("", false),
+            RenameRule::Upper => join("", true),
+            RenameRule::Pascal => words.iter().map(capitalize).collect(),
+            RenameRule::Camel => {
+                let pascal: String = words.iter().skip(1).map(capitalize).collect();
+                match words.first() {
+                    Some(first) => first.to_lowercase() + &pascal,
+                    None => pascal,
+                }
+            }
+            RenameRule::Snake => join("_", false),
+            RenameRule::ScreamingSnake => join("_", true),
+            RenameRule::Kebab => join("-", false),
+            RenameRule::ScreamingKebab => join("-", true),
+        }
+    }
+}
+
+/// Converts an identifier like `HttpError` or `HTTPError` into `http_error`.
+pub fn to_snake_case(ident: &Ident) -> String {
+    RenameRule::Snake.apply(ident)
+}
+
+/// Returns the name of a variant as used in generated method names like `is_foo` or `unwrap_foo`.
+/// This is the snake_case variant name, unless it's overridden with `#[attr_name(rename = "...")]`.
+/// Returns `None` if the variant is marked with `#[attr_name(ignore)]`.
+pub fn variant_method_name(variant: &Variant, attr_name: &str) -> Result<Option<String>> {
+    let mut name = to_snake_case(&variant.ident);
+    for arg in get_attr_args(&variant.attrs, attr_name)?.unwrap_or_default() {
+        if is_flag(&arg, "ignore") {
+            return Ok(None);
+        }
+        match get_str_arg(&arg, "rename")? {
+            Some(rename) => name = rename.value(),
+            None => {
+                return Err(Error::new(
+                    arg.span(),
+                    format!(
+                        "Proper syntax: #[{}(ignore)] or #[{}(rename = \"...\")]",
+                        attr_name, attr_name
+                    ),
+                ))
+            }
+        }
+    }
+    // Make sure the generated method names are valid identifiers
+    if syn::parse_str::<Ident>(&format!("is_{}", name)).is_err() {
+        return Err(Error::new(
+            variant.span(),
+            format!("`{}` cannot be used in a method name", name),
+        ));
+    }
+    Ok(Some(name))
+}
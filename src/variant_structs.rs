@@ -0,0 +1,181 @@
+use crate::utils::{get_attr_args, get_derive_arg, get_str_arg, is_flag, uses_generic_param};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{Error, Result},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    token::Comma,
+    Data, DeriveInput, Field, Fields, Generics, Ident, Path, Variant,
+};
+
+/// Provides the hook to expand `#[derive(VariantStructs)]` into a struct for every variant with
+/// named fields, together with conversions between those structs and the enum
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
+    let data_enum = match input.data {
+        Data::Enum(ref data_enum) => data_enum,
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                format!("Only enums can derive({})", trait_name),
+            ))
+        }
+    };
+    let enum_name = &input.ident;
+    let vis = &input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut derives: Vec<Path> = vec![];
+    for arg in get_attr_args(&input.attrs, "variant_structs")?.unwrap_or_default() {
+        match get_derive_arg(&arg)? {
+            Some(paths) => derives.extend(paths),
+            None => {
+                return Err(Error::new(
+                    arg.span(),
+                    "Proper syntax: #[variant_structs(derive(...))]",
+                ))
+            }
+        }
+    }
+
+    let mut tokens = TokenStream::new();
+    for variant in &data_enum.variants {
+        let fields = match variant.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => continue,
+        };
+        let struct_name = match struct_name(variant)? {
+            Some(name) => name,
+            None => continue,
+        };
+        let variant_ident = &variant.ident;
+        let struct_generics = variant_generics(&input.generics, fields);
+        let (_, struct_ty_generics, struct_where_clause) = struct_generics.split_for_impl();
+        let docs = variant.attrs.iter().filter(|a| a.path.is_ident("doc"));
+        let field_idents = &fields
+            .iter()
+            .map(|f| f.ident.as_ref().unwrap())
+            .collect::<Vec<_>>();
+        let field_defs = fields.iter().map(|f| {
+            let field_docs = f.attrs.iter().filter(|a| a.path.is_ident("doc"));
+            let ident = &f.ident;
+            let ty = &f.ty;
+            quote!(#(#field_docs)* #vis #ident: #ty)
+        });
+        let derive = if derives.is_empty() {
+            TokenStream::new()
+        } else {
+            quote!(#[derive(#(#derives),*)])
+        };
+
+        quote! {
+            #(#docs)*
+            #derive
+            #vis struct #struct_name #struct_generics #struct_where_clause {
+                #(#field_defs),*
+            }
+
+            impl#impl_generics ::core::convert::From<#struct_name#struct_ty_generics>
+                for #enum_name#ty_generics #where_clause
+            {
+                #[inline]
+                fn from(value: #struct_name#struct_ty_generics) -> #enum_name#ty_generics {
+                    #enum_name::#variant_ident {
+                        #(#field_idents: value.#field_idents),*
+                    }
+                }
+            }
+
+            impl#impl_generics ::core::convert::TryFrom<#enum_name#ty_generics>
+                for #struct_name#struct_ty_generics #where_clause
+            {
+                type Error = #enum_name#ty_generics;
+
+                #[inline]
+                #[allow(unreachable_patterns)]
+                fn try_from(
+                    value: #enum_name#ty_generics,
+                ) -> ::core::result::Result<#struct_name#struct_ty_generics, #enum_name#ty_generics> {
+                    match value {
+                        #enum_name::#variant_ident { #(#field_idents),* } => {
+                            ::core::result::Result::Ok(#struct_name { #(#field_idents),* })
+                        }
+                        other => ::core::result::Result::Err(other),
+                    }
+                }
+            }
+        }
+        .to_tokens(&mut tokens);
+    }
+    Ok(tokens)
+}
+
+/// Returns the generics of the enum that are used by the fields of a variant, which become the
+/// generics of its struct. Parameters that are only used by other variants are left out, because
+/// structs can't have unused parameters, and so are the where clauses that mention them.
+fn variant_generics(generics: &Generics, fields: &Punctuated<Field, Comma>) -> Generics {
+    let mut used_tokens: TokenStream = fields.iter().map(|f| f.ty.to_token_stream()).collect();
+    let mut used = vec![false; generics.params.len()];
+    // The bounds of a used parameter can mention other parameters, which are needed as well
+    loop {
+        let mut changed = false;
+        for (param, used) in generics.params.iter().zip(&mut used) {
+            if !*used && uses_generic_param(used_tokens.clone(), param) {
+                *used = true;
+                changed = true;
+                used_tokens.extend(param.to_token_stream());
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let mut variant_generics = generics.clone();
+    variant_generics.params = generics
+        .params
+        .iter()
+        .zip(&used)
+        .filter(|(_, &used)| used)
+        .map(|(param, _)| param.clone())
+        .collect();
+    let unused: Vec<_> = generics
+        .params
+        .iter()
+        .zip(&used)
+        .filter(|(_, &used)| !used)
+        .map(|(param, _)| param)
+        .collect();
+    if let Some(ref mut where_clause) = variant_generics.where_clause {
+        where_clause.predicates = where_clause
+            .predicates
+            .iter()
+            .filter(|predicate| {
+                let tokens = predicate.to_token_stream();
+                !unused
+                    .iter()
+                    .any(|param| uses_generic_param(tokens.clone(), param))
+            })
+            .cloned()
+            .collect();
+    }
+    variant_generics
+}
+
+/// Returns the name of the struct for a variant, or `None` if the variant is ignored.
+fn struct_name(variant: &Variant) -> Result<Option<Ident>> {
+    let mut name = variant.ident.clone();
+    for arg in get_attr_args(&variant.attrs, "variant_structs")?.unwrap_or_default() {
+        if is_flag(&arg, "ignore") {
+            return Ok(None);
+        }
+        match get_str_arg(&arg, "name")? {
+            Some(lit) => name = lit.parse()?,
+            None => return Err(Error::new(
+                arg.span(),
+                "Proper syntax: #[variant_structs(ignore)] or #[variant_structs(name = \"...\")]",
+            )),
+        }
+    }
+    Ok(Some(name))
+}
@@ -0,0 +1,112 @@
+use crate::utils::{get_attr_args, get_str_arg, unraw, RenameRule};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Error, Result},
+    spanned::Spanned,
+    Data, DeriveInput, Fields, LitStr,
+};
+
+/// Provides the hook to expand `#[derive(Variants)]` into a `variant_name` method and, for
+/// fieldless enums, into constants and methods to iterate over and index the variants
+pub fn expand(input: &DeriveInput, trait_name: &str) -> Result<TokenStream> {
+    let data_enum = match input.data {
+        Data::Enum(ref data_enum) => data_enum,
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                format!("Only enums can derive({})", trait_name),
+            ))
+        }
+    };
+    let enum_name = &input.ident;
+    let vis = &input.vis;
+
+    let mut rename_all = None;
+    for arg in get_attr_args(&input.attrs, "variants")?.unwrap_or_default() {
+        match get_str_arg(&arg, "rename_all")? {
+            Some(rule) => rename_all = Some(RenameRule::parse(&rule)?),
+            None => {
+                return Err(Error::new(
+                    arg.span(),
+                    "Proper syntax: #[variants(rename_all = \"...\")]",
+                ))
+            }
+        }
+    }
+
+    let mut names = vec![];
+    for variant in &data_enum.variants {
+        let mut name = match rename_all {
+            Some(rule) => rule.apply(&variant.ident),
+            None => unraw(&variant.ident).value(),
+        };
+        for arg in get_attr_args(&variant.attrs, "variants")?.unwrap_or_default() {
+            match get_str_arg(&arg, "rename")? {
+                Some(rename) => name = rename.value(),
+                None => {
+                    return Err(Error::new(
+                        arg.span(),
+                        "Proper syntax: #[variants(rename = \"...\")]",
+                    ))
+                }
+            }
+        }
+        names.push(LitStr::new(&name, variant.ident.span()));
+    }
+    let variant_idents: &Vec<_> = &data_enum.variants.iter().map(|v| &v.ident).collect();
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let variant_name = quote! {
+        /// Returns the name of the variant of this value.
+        #[inline]
+        #vis const fn variant_name(&self) -> &'static str {
+            match *self {
+                #(#enum_name::#variant_idents { .. } => #names),*
+            }
+        }
+    };
+
+    let fieldless = data_enum
+        .variants
+        .iter()
+        .all(|v| matches!(v.fields, Fields::Unit));
+    let fieldless_items = if fieldless {
+        let count = variant_idents.len();
+        let indices = &(0..count).collect::<Vec<_>>();
+        quote! {
+            /// All variants, in the order in which they are declared.
+            #vis const VARIANTS: &'static [Self] = &[#(#enum_name::#variant_idents),*];
+
+            /// The number of variants.
+            #vis const COUNT: usize = #count;
+
+            /// Returns the position of this variant in `VARIANTS`.
+            #[inline]
+            #vis const fn index(&self) -> usize {
+                match *self {
+                    #(#enum_name::#variant_idents => #indices),*
+                }
+            }
+
+            /// Returns the variant at position `index` in `VARIANTS`, or `None` if `index` is out
+            /// of bounds.
+            #[inline]
+            #vis const fn from_index(index: usize) -> ::core::option::Option<Self> {
+                match index {
+                    #(#indices => ::core::option::Option::Some(#enum_name::#variant_idents),)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    Ok(quote! {
+        impl#impl_generics #enum_name#ty_generics #where_clause {
+            #variant_name
+            #fieldless_items
+        }
+    })
+}
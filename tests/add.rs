@@ -21,3 +21,45 @@ enum MixedInts {
     UnsignedTwo(u32),
     Unit,
 }
+
+#[derive(Add, Sub, Debug, PartialEq)]
+struct Meters<Unit> {
+    value: i32,
+    #[add(skip)]
+    unit: ::std::marker::PhantomData<Unit>,
+}
+
+#[derive(Add, Debug, PartialEq)]
+struct Tagged(#[add(skip)] &'static str, i32);
+
+#[derive(Add, Debug, PartialEq)]
+enum MixedSkipped {
+    Tagged(#[add(skip)] u64, i32),
+    Named {
+        #[add(skip)]
+        id: u64,
+        x: i32,
+    },
+}
+
+#[test]
+fn add_skipped_fields() {
+    struct Km;
+    let meters = Meters::<Km> {
+        value: 1,
+        unit: ::std::marker::PhantomData,
+    } + Meters {
+        value: 2,
+        unit: ::std::marker::PhantomData,
+    };
+    assert_eq!(3, meters.value);
+    assert_eq!(Tagged("left", 3), Tagged("left", 1) + Tagged("right", 2));
+    assert_eq!(
+        Ok(MixedSkipped::Tagged(1, 3)),
+        MixedSkipped::Tagged(1, 1) + MixedSkipped::Tagged(2, 2)
+    );
+    assert_eq!(
+        Ok(MixedSkipped::Named { id: 1, x: 3 }),
+        MixedSkipped::Named { id: 1, x: 1 } + MixedSkipped::Named { id: 2, x: 2 }
+    );
+}
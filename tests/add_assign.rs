@@ -10,3 +10,31 @@ struct Point2D {
     x: i32,
     y: i32,
 }
+
+#[derive(AddAssign)]
+struct Meters<Unit> {
+    value: i32,
+    #[add(skip)]
+    unit: ::std::marker::PhantomData<Unit>,
+}
+
+#[derive(AddAssign)]
+struct Counter {
+    #[add(skip)]
+    id: String,
+    count: u32,
+}
+
+#[test]
+fn add_assign_skipped_fields() {
+    let mut counter = Counter {
+        id: "left".to_owned(),
+        count: 1,
+    };
+    counter += Counter {
+        id: "right".to_owned(),
+        count: 2,
+    };
+    assert_eq!("left", counter.id);
+    assert_eq!(3, counter.count);
+}
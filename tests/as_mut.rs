@@ -0,0 +1,82 @@
+#![allow(dead_code)]
+#[macro_use]
+extern crate derive_more;
+
+use std::path::PathBuf;
+
+#[derive(AsMut)]
+struct SingleFieldTuple(String);
+
+#[derive(AsMut)]
+struct MultiFieldNamed {
+    #[as_mut]
+    first: String,
+    #[as_mut]
+    second: PathBuf,
+    third: Vec<usize>,
+}
+
+#[derive(AsMut)]
+#[as_mut(forward)]
+struct Forwarded(Vec<u8>);
+
+#[derive(AsRef, AsMut)]
+struct Both {
+    #[as_ref]
+    #[as_mut]
+    inner: String,
+    #[as_ref]
+    len: usize,
+}
+
+#[test]
+fn single_field_tuple() {
+    let mut item = SingleFieldTuple(String::from("test"));
+    AsMut::<String>::as_mut(&mut item).push('s');
+    assert_eq!(item.0, "tests");
+}
+
+#[test]
+fn multi_field_named() {
+    let mut item = MultiFieldNamed {
+        first: String::from("test"),
+        second: PathBuf::new(),
+        third: vec![],
+    };
+    AsMut::<String>::as_mut(&mut item).push('s');
+    AsMut::<PathBuf>::as_mut(&mut item).push("dir");
+    assert_eq!(item.first, "tests");
+    assert_eq!(item.second, PathBuf::from("dir"));
+}
+
+#[test]
+fn forward() {
+    let mut item = Forwarded(vec![1, 2, 3]);
+    AsMut::<[u8]>::as_mut(&mut item)[0] = 4;
+    AsMut::<Vec<u8>>::as_mut(&mut item).push(5);
+    assert_eq!(item.0, [4, 2, 3, 5]);
+}
+
+#[test]
+fn with_as_ref() {
+    let mut item = Both {
+        inner: String::from("test"),
+        len: 4,
+    };
+    AsMut::<String>::as_mut(&mut item).push('s');
+    assert_eq!(AsRef::<String>::as_ref(&item), "tests");
+    assert_eq!(AsRef::<usize>::as_ref(&item), &4);
+}
+
+#[derive(AsMut)]
+#[as_mut(array)]
+#[repr(C)]
+struct Rgba<T>(T, T, T, T);
+
+#[test]
+fn array() {
+    let mut color = Rgba(1u16, 2, 3, 4);
+    let array: &mut [u16; 4] = color.as_mut();
+    array[3] = 5;
+    assert_eq!(color.3, 5);
+}
@@ -0,0 +1,129 @@
+#![allow(dead_code)]
+#[macro_use]
+extern crate derive_more;
+
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+#[derive(AsRef)]
+struct SingleFieldTuple(String);
+
+#[derive(AsRef)]
+struct SingleFieldNamed {
+    first: String,
+}
+
+#[derive(AsRef)]
+struct MultiFieldTuple(#[as_ref] String, #[as_ref] PathBuf, Vec<usize>);
+
+#[derive(AsRef)]
+struct MultiFieldNamed {
+    #[as_ref]
+    first: String,
+    #[as_ref]
+    second: PathBuf,
+    third: Vec<usize>,
+}
+
+#[derive(AsRef)]
+#[as_ref(forward)]
+struct ForwardedPath(PathBuf);
+
+#[derive(AsRef)]
+struct ForwardedField {
+    #[as_ref(forward)]
+    name: String,
+    len: usize,
+}
+
+#[derive(AsRef)]
+struct Generic<T> {
+    #[as_ref]
+    value: T,
+    other: u8,
+}
+
+#[derive(AsRef)]
+#[as_ref(forward)]
+struct GenericForwarded<T>(T);
+
+fn as_str<T: AsRef<str> + ?Sized>(x: &T) -> &str {
+    x.as_ref()
+}
+
+#[test]
+fn single_field_tuple() {
+    let item = SingleFieldTuple(String::from("test"));
+    assert!(std::ptr::eq(&item.0, AsRef::<String>::as_ref(&item)));
+}
+
+#[test]
+fn single_field_named() {
+    let item = SingleFieldNamed {
+        first: String::from("test"),
+    };
+    assert!(std::ptr::eq(&item.first, AsRef::<String>::as_ref(&item)));
+}
+
+#[test]
+fn multi_field_tuple() {
+    let item = MultiFieldTuple(String::from("test"), PathBuf::new(), vec![]);
+    assert!(std::ptr::eq(&item.0, AsRef::<String>::as_ref(&item)));
+    assert!(std::ptr::eq(&item.1, AsRef::<PathBuf>::as_ref(&item)));
+}
+
+#[test]
+fn multi_field_named() {
+    let item = MultiFieldNamed {
+        first: String::from("test"),
+        second: PathBuf::new(),
+        third: vec![],
+    };
+    assert!(std::ptr::eq(&item.first, AsRef::<String>::as_ref(&item)));
+    assert!(std::ptr::eq(&item.second, AsRef::<PathBuf>::as_ref(&item)));
+}
+
+#[test]
+fn forward() {
+    let item = ForwardedPath(PathBuf::from("/tmp/file"));
+    assert_eq!(AsRef::<Path>::as_ref(&item), Path::new("/tmp/file"));
+    assert_eq!(AsRef::<OsStr>::as_ref(&item), OsStr::new("/tmp/file"));
+    let item = ForwardedField {
+        name: String::from("name"),
+        len: 4,
+    };
+    assert_eq!(as_str(&item), "name");
+    let bytes: &[u8] = item.as_ref();
+    assert_eq!(bytes, b"name");
+}
+
+#[test]
+fn generic() {
+    let item = Generic {
+        value: 1u32,
+        other: 2,
+    };
+    assert_eq!(AsRef::<u32>::as_ref(&item), &1);
+    assert_eq!(as_str(&GenericForwarded("forwarded")), "forwarded");
+    assert_eq!(as_str(&GenericForwarded(String::from("owned"))), "owned");
+}
+
+#[derive(AsRef)]
+#[as_ref(array)]
+#[repr(C)]
+struct Vec3 {
+    x: f32,
+    y: f32,
+    z: f32,
+}
+
+#[test]
+fn array() {
+    let v = Vec3 {
+        x: 1.0,
+        y: 2.0,
+        z: 3.0,
+    };
+    let array: &[f32; 3] = v.as_ref();
+    assert_eq!(array, &[1.0, 2.0, 3.0]);
+}
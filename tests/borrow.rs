@@ -0,0 +1,61 @@
+#![allow(dead_code, unused_imports)]
+#[macro_use]
+extern crate derive_more;
+
+use std::borrow::{Borrow, BorrowMut};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+#[derive(Borrow, BorrowMut)]
+struct MyInt(i32);
+
+#[derive(Borrow, PartialEq, Eq, Hash)]
+#[borrow(types(str, String))]
+struct Key(String);
+
+#[derive(Borrow)]
+#[borrow(forward)]
+struct MyPath(PathBuf);
+
+#[derive(Borrow, BorrowMut)]
+struct Record {
+    id: u64,
+    #[borrow(types("[u8]", "Vec<u8>"))]
+    data: Vec<u8>,
+}
+
+#[derive(Borrow)]
+struct Wrapper<T>(T);
+
+#[test]
+fn field() {
+    let mut int = MyInt(1);
+    *BorrowMut::<i32>::borrow_mut(&mut int) += 1;
+    assert_eq!(2, *Borrow::<i32>::borrow(&int));
+
+    let wrapper = Wrapper("a");
+    assert_eq!("a", *Borrow::<&str>::borrow(&wrapper));
+}
+
+#[test]
+fn types() {
+    let mut map = HashMap::new();
+    map.insert(Key("a".to_owned()), 1);
+    assert_eq!(Some(&1), map.get("a"));
+    assert_eq!("a", Borrow::<String>::borrow(&Key("a".to_owned())));
+
+    let mut record = Record {
+        id: 1,
+        data: vec![1, 2],
+    };
+    BorrowMut::<[u8]>::borrow_mut(&mut record)[0] = 3;
+    BorrowMut::<Vec<u8>>::borrow_mut(&mut record).push(4);
+    assert_eq!(&[3, 2, 4], Borrow::<[u8]>::borrow(&record));
+}
+
+#[test]
+fn forward() {
+    let path = MyPath(PathBuf::from("/tmp"));
+    assert_eq!(Path::new("/tmp"), Borrow::<Path>::borrow(&path));
+    assert_eq!(&PathBuf::from("/tmp"), Borrow::<PathBuf>::borrow(&path));
+}
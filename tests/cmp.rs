@@ -0,0 +1,216 @@
+#![allow(dead_code, unused_imports)]
+#[macro_use]
+extern crate derive_more;
+
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn lowercase(s: &str) -> String {
+    s.to_lowercase()
+}
+
+#[derive(
+    PartialEqCustom,
+    EqCustom,
+    HashCustom,
+    PartialOrdCustom,
+    OrdCustom,
+    Debug
+)]
+struct Entity {
+    id: u64,
+    #[cmp(skip)]
+    cached_hash: u64,
+    #[cmp(skip)]
+    updated_at: u64,
+}
+
+#[derive(PartialEqCustom, EqCustom, HashCustom, Debug)]
+struct Tag(#[cmp(by = "lowercase")] String);
+
+#[derive(PartialEqCustom, PartialOrdCustom, EqCustom, OrdCustom, Debug)]
+struct Person {
+    first_name: &'static str,
+    #[cmp(order = 0)]
+    last_name: &'static str,
+}
+
+#[derive(
+    PartialEqCustom,
+    EqCustom,
+    HashCustom,
+    PartialOrdCustom,
+    OrdCustom,
+    Debug
+)]
+enum Shape {
+    Circle {
+        radius: u32,
+        #[cmp(skip)]
+        label: &'static str,
+    },
+    Square(u32, #[cmp(skip)] f64),
+    Point,
+}
+
+#[derive(
+    PartialEqCustom,
+    EqCustom,
+    HashCustom,
+    PartialOrdCustom,
+    OrdCustom,
+    Debug
+)]
+struct Generic<T> {
+    value: T,
+    #[cmp(skip)]
+    note: NotComparable,
+}
+
+#[derive(Debug, Default)]
+struct NotComparable;
+
+#[derive(
+    PartialEqCustom,
+    EqCustom,
+    HashCustom,
+    PartialOrdCustom,
+    OrdCustom,
+    Debug
+)]
+struct Containers<T, U> {
+    list: Vec<T>,
+    pair: (Option<T>, Box<U>),
+    #[cmp(skip)]
+    cache: NotComparable,
+}
+
+#[derive(PartialEqCustom, EqCustom, HashCustom, PartialOrdCustom, OrdCustom)]
+struct Unit;
+
+#[derive(PartialEqCustom, EqCustom, HashCustom, PartialOrdCustom, OrdCustom)]
+enum Empty {}
+
+#[test]
+fn skip() {
+    let a = Entity {
+        id: 1,
+        cached_hash: 10,
+        updated_at: 100,
+    };
+    let b = Entity {
+        id: 1,
+        cached_hash: 20,
+        updated_at: 200,
+    };
+    let c = Entity {
+        id: 2,
+        cached_hash: 10,
+        updated_at: 100,
+    };
+    assert_eq!(a, b);
+    assert_eq!(hash(&a), hash(&b));
+    assert_ne!(a, c);
+    assert_eq!(Ordering::Equal, a.cmp(&b));
+    assert!(a < c);
+}
+
+#[test]
+fn by() {
+    assert_eq!(Tag("Rust".to_owned()), Tag("rust".to_owned()));
+    assert_eq!(hash(&Tag("Rust".to_owned())), hash(&Tag("RUST".to_owned())));
+    assert_ne!(Tag("Rust".to_owned()), Tag("C".to_owned()));
+    let tags: HashSet<_> = vec![Tag("a".to_owned()), Tag("A".to_owned())]
+        .into_iter()
+        .collect();
+    assert_eq!(1, tags.len());
+}
+
+#[test]
+fn order() {
+    let ada = Person {
+        first_name: "Ada",
+        last_name: "Lovelace",
+    };
+    let alan = Person {
+        first_name: "Alan",
+        last_name: "Turing",
+    };
+    let zed = Person {
+        first_name: "Zed",
+        last_name: "Lovelace",
+    };
+    assert!(ada < alan);
+    assert!(zed < alan);
+    assert!(ada < zed);
+}
+
+#[test]
+fn enums() {
+    let circle = Shape::Circle {
+        radius: 1,
+        label: "a",
+    };
+    let other_circle = Shape::Circle {
+        radius: 1,
+        label: "b",
+    };
+    assert_eq!(circle, other_circle);
+    assert_eq!(hash(&circle), hash(&other_circle));
+    assert_eq!(Shape::Square(2, 1.0), Shape::Square(2, 2.0));
+    assert_ne!(circle, Shape::Square(1, 1.0));
+    assert_ne!(hash(&Shape::Point), hash(&circle));
+    assert!(circle < Shape::Square(0, 0.0));
+    assert!(Shape::Square(3, 0.0) < Shape::Point);
+    assert!(Shape::Square(2, 0.0) < Shape::Square(3, 0.0));
+    assert_eq!(
+        Some(Ordering::Greater),
+        Shape::Point.partial_cmp(&Shape::Square(1, 0.0))
+    );
+}
+
+#[test]
+fn generic() {
+    let a = Generic {
+        value: 1,
+        note: NotComparable,
+    };
+    let b = Generic {
+        value: 2,
+        note: NotComparable,
+    };
+    assert!(a < b);
+    assert_ne!(a, b);
+    assert_eq!(
+        hash(&a),
+        hash(&Generic {
+            value: 1,
+            note: NotComparable
+        })
+    );
+    assert!(Unit == Unit);
+    assert_eq!(Ordering::Equal, Unit.cmp(&Unit));
+}
+
+#[test]
+fn generic_containers() {
+    let containers = |list: Vec<u8>, second: &'static str| Containers {
+        list,
+        pair: (None, Box::new(second)),
+        cache: NotComparable,
+    };
+    let a = containers(vec![1, 2], "a");
+    assert_eq!(a, containers(vec![1, 2], "a"));
+    assert_ne!(a, containers(vec![1, 2], "b"));
+    assert!(a < containers(vec![1, 3], "a"));
+    assert_eq!(Ordering::Less, a.cmp(&containers(vec![1, 2], "b")));
+    assert_eq!(hash(&a), hash(&containers(vec![1, 2], "a")));
+}
@@ -0,0 +1,159 @@
+#![allow(dead_code)]
+#[macro_use]
+extern crate derive_more;
+
+use std::fmt;
+
+#[derive(DebugCustom)]
+struct Unit;
+
+#[derive(DebugCustom)]
+struct Tuple(i32, &'static str);
+
+#[derive(DebugCustom)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(DebugCustom)]
+struct Config {
+    user: &'static str,
+    #[debug(redact)]
+    password: &'static str,
+    #[debug(skip)]
+    cache: Vec<u8>,
+    #[debug(fmt = "{:#x}")]
+    flags: u32,
+    #[debug(with = "fmt_millis")]
+    timeout: u64,
+}
+
+fn fmt_millis(ms: &u64, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}ms", ms)
+}
+
+#[derive(DebugCustom)]
+#[debug(transparent)]
+struct Transparent(Point);
+
+#[derive(DebugCustom)]
+enum Message {
+    Quit,
+    Move {
+        x: i32,
+        y: i32,
+    },
+    Write(&'static str),
+    #[debug(fmt = "color #{:02x}{:02x}{:02x}", _0, _1, _2)]
+    Color(u8, u8, u8),
+    #[debug(transparent)]
+    Forward(Point),
+    Secret(#[debug(redact)] u64, #[debug(skip)] u64),
+}
+
+#[derive(DebugCustom)]
+struct Generic<T, U> {
+    value: T,
+    #[debug(skip)]
+    hidden: U,
+}
+
+struct NotDebug;
+
+#[derive(DebugCustom)]
+struct Shadowing {
+    #[debug(fmt = "{:x}")]
+    f: u32,
+    #[debug(with = "fmt_millis")]
+    g: u64,
+}
+
+#[derive(DebugCustom)]
+#[debug(fmt = "<{}>")]
+enum ShadowingEnum {
+    Hex {
+        #[debug(fmt = "{:x}")]
+        f: u32,
+    },
+    Millis(#[debug(with = "fmt_millis")] u64),
+}
+
+#[test]
+fn unit() {
+    assert_eq!(format!("{:?}", Unit), "Unit");
+}
+
+#[test]
+fn structs() {
+    assert_eq!(format!("{:?}", Tuple(1, "a")), r#"Tuple(1, "a")"#);
+    assert_eq!(
+        format!("{:?}", Point { x: 1, y: 2 }),
+        "Point { x: 1, y: 2 }"
+    );
+    assert_eq!(
+        format!("{:#?}", Point { x: 1, y: 2 }),
+        "Point {\n    x: 1,\n    y: 2,\n}"
+    );
+}
+
+#[test]
+fn field_attributes() {
+    let config = Config {
+        user: "root",
+        password: "hunter2",
+        cache: vec![1, 2, 3],
+        flags: 255,
+        timeout: 30,
+    };
+    assert_eq!(
+        format!("{:?}", config),
+        r#"Config { user: "root", password: <redacted>, flags: 0xff, timeout: 30ms }"#
+    );
+}
+
+#[test]
+fn transparent() {
+    assert_eq!(
+        format!("{:?}", Transparent(Point { x: 1, y: 2 })),
+        "Point { x: 1, y: 2 }"
+    );
+}
+
+#[test]
+fn enums() {
+    assert_eq!(format!("{:?}", Message::Quit), "Quit");
+    assert_eq!(
+        format!("{:?}", Message::Move { x: 1, y: 2 }),
+        "Move { x: 1, y: 2 }"
+    );
+    assert_eq!(format!("{:?}", Message::Write("hi")), r#"Write("hi")"#);
+    assert_eq!(format!("{:?}", Message::Color(255, 0, 16)), "color #ff0010");
+    assert_eq!(
+        format!("{:?}", Message::Forward(Point { x: 1, y: 2 })),
+        "Point { x: 1, y: 2 }"
+    );
+    assert_eq!(format!("{:?}", Message::Secret(1, 2)), "Secret(<redacted>)");
+}
+
+#[test]
+fn generic() {
+    let g = Generic {
+        value: 1,
+        hidden: NotDebug,
+    };
+    assert_eq!(format!("{:?}", g), "Generic { value: 1 }");
+}
+
+#[test]
+fn field_named_f() {
+    assert_eq!(
+        format!("{:?}", Shadowing { f: 255, g: 3 }),
+        "Shadowing { f: ff, g: 3ms }"
+    );
+    assert_eq!(
+        format!("{:?}", ShadowingEnum::Hex { f: 255 }),
+        "<Hex { f: ff }>"
+    );
+    assert_eq!(format!("{:?}", ShadowingEnum::Millis(3)), "<Millis(3ms)>");
+}
@@ -0,0 +1,104 @@
+#![allow(dead_code, unused_imports)]
+#[macro_use]
+extern crate derive_more;
+
+use std::marker::PhantomData;
+
+#[derive(DefaultCustom, Debug, PartialEq)]
+struct Config {
+    #[default(8080)]
+    port: u16,
+    #[default = "localhost"]
+    host: String,
+    #[default(vec!["a".to_owned(), "b".to_owned()])]
+    tags: Vec<String>,
+    verbose: bool,
+}
+
+#[derive(DefaultCustom, Debug, PartialEq)]
+struct Point(#[default(1)] i32, i32);
+
+#[derive(DefaultCustom, Debug, PartialEq)]
+enum Mode {
+    Off,
+    #[default]
+    On {
+        #[default(3)]
+        level: u8,
+        name: String,
+    },
+}
+
+#[derive(DefaultCustom, Debug, PartialEq)]
+enum Simple {
+    A,
+    #[default]
+    B,
+}
+
+#[derive(Debug, PartialEq)]
+struct NoDefault;
+
+#[derive(DefaultCustom, Debug, PartialEq)]
+struct Inferred<T, U> {
+    value: T,
+    #[default(None)]
+    other: Option<U>,
+    marker: PhantomData<U>,
+}
+
+#[derive(DefaultCustom, Debug, PartialEq)]
+struct Boxed<T> {
+    value: Box<T>,
+    list: Vec<T>,
+}
+
+#[derive(DefaultCustom, Debug)]
+#[default(bound = "")]
+struct Handle<T> {
+    id: u64,
+    marker: PhantomData<T>,
+}
+
+#[derive(DefaultCustom, Debug)]
+#[default(bound = "T: Clone + Default")]
+struct Bounded<T>(T);
+
+#[test]
+fn structs() {
+    assert_eq!(
+        Config {
+            port: 8080,
+            host: "localhost".to_owned(),
+            tags: vec!["a".to_owned(), "b".to_owned()],
+            verbose: false,
+        },
+        Config::default()
+    );
+    assert_eq!(Point(1, 0), Point::default());
+}
+
+#[test]
+fn enums() {
+    assert_eq!(
+        Mode::On {
+            level: 3,
+            name: String::new(),
+        },
+        Mode::default()
+    );
+    assert_eq!(Simple::B, Simple::default());
+}
+
+#[test]
+fn bounds() {
+    let inferred: Inferred<i32, NoDefault> = Inferred::default();
+    assert_eq!(0, inferred.value);
+    assert_eq!(None, inferred.other);
+    let boxed: Boxed<u8> = Boxed::default();
+    assert_eq!(Box::new(0), boxed.value);
+    assert!(boxed.list.is_empty());
+    let handle: Handle<NoDefault> = Handle::default();
+    assert_eq!(0, handle.id);
+    assert_eq!(String::new(), Bounded::<String>::default().0);
+}
@@ -3,9 +3,91 @@
 extern crate derive_more;
 
 #[derive(Deref)]
+#[deref(forward)]
 struct MyBoxedInt(Box<i32>);
 
 #[derive(Deref)]
+#[deref(forward)]
 struct NumRef<'a> {
     num: &'a i32,
 }
+
+#[derive(Deref)]
+struct MyInt(i32);
+
+#[derive(Deref)]
+struct Point1D {
+    x: i32,
+}
+
+#[derive(Deref)]
+struct CoolVec {
+    cool: bool,
+    #[deref]
+    vec: Vec<i32>,
+}
+
+#[derive(Deref)]
+struct ForwardedVec {
+    len: usize,
+    #[deref(forward)]
+    vec: Vec<i32>,
+}
+
+#[derive(Deref)]
+struct Generic<T>(T);
+
+#[test]
+fn deref() {
+    assert_eq!(*MyBoxedInt(Box::new(1)), 1);
+    assert_eq!(*NumRef { num: &2 }, 2);
+    assert_eq!(*MyInt(3), 3);
+    assert_eq!(*Point1D { x: 4 }, 4);
+
+    let cool_vec = CoolVec {
+        cool: true,
+        vec: vec![1, 2],
+    };
+    let vec: &Vec<i32> = &cool_vec;
+    assert_eq!(vec.capacity(), cool_vec.vec.capacity());
+
+    let forwarded = ForwardedVec {
+        len: 2,
+        vec: vec![1, 2],
+    };
+    let slice: &[i32] = &forwarded;
+    assert_eq!(slice, [1, 2]);
+    assert_eq!(*Generic("str"), "str");
+}
+
+#[derive(Deref)]
+enum Buffer {
+    Heap(Vec<u8>),
+    Mapped {
+        len: usize,
+        #[deref]
+        data: Vec<u8>,
+    },
+}
+
+#[derive(Deref)]
+#[deref(forward)]
+enum AnyStr<'a> {
+    Owned(String),
+    Borrowed(&'a str, #[deref] String),
+}
+
+#[test]
+fn deref_enum() {
+    let heap = Buffer::Heap(vec![1, 2]);
+    let vec: &Vec<u8> = &heap;
+    assert_eq!(vec, &[1, 2]);
+    let mapped = Buffer::Mapped {
+        len: 1,
+        data: vec![3],
+    };
+    assert_eq!(*mapped, [3]);
+
+    assert_eq!(&*AnyStr::Owned("owned".to_string()), "owned");
+    assert_eq!(&*AnyStr::Borrowed("a", "b".to_string()), "b");
+}
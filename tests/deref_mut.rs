@@ -2,18 +2,12 @@
 #[macro_use]
 extern crate derive_more;
 
-#[derive(DerefMut)]
+#[derive(Deref, DerefMut)]
+#[deref(forward)]
 struct MyBoxedInt(Box<i32>);
-// Deref implementation is needed for DerefMut
-impl ::std::ops::Deref for MyBoxedInt {
-    type Target = <Box<i32> as ::std::ops::Deref>::Target;
-    #[inline]
-    fn deref(&self) -> &Self::Target {
-        <Box<i32> as ::std::ops::Deref>::deref(&self.0)
-    }
-}
 
 #[derive(DerefMut)]
+#[deref(forward)]
 struct NumRef<'a> {
     num: &'a mut i32,
 }
@@ -25,3 +19,91 @@ impl<'a> ::std::ops::Deref for NumRef<'a> {
         <&'a mut i32 as ::std::ops::Deref>::deref(&self.num)
     }
 }
+
+#[derive(DerefMut)]
+struct MyInt(i32);
+
+// Deref implementation is needed for DerefMut
+impl ::std::ops::Deref for MyInt {
+    type Target = i32;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[derive(Deref, DerefMut)]
+struct Point1D {
+    x: i32,
+}
+
+#[derive(Deref, DerefMut)]
+struct CoolVec {
+    cool: bool,
+    #[deref]
+    vec: Vec<i32>,
+}
+
+#[derive(Deref, DerefMut)]
+struct ForwardedVec {
+    len: usize,
+    #[deref(forward)]
+    vec: Vec<i32>,
+}
+
+#[test]
+fn deref_mut() {
+    let mut boxed = MyBoxedInt(Box::new(1));
+    *boxed = 2;
+    assert_eq!(*boxed.0, 2);
+
+    let mut num = 1;
+    *NumRef { num: &mut num } = 3;
+    assert_eq!(num, 3);
+
+    let mut int = MyInt(1);
+    *int = 4;
+    assert_eq!(int.0, 4);
+
+    let mut point = Point1D { x: 1 };
+    *point += 1;
+    assert_eq!(point.x, 2);
+
+    let mut cool_vec = CoolVec {
+        cool: true,
+        vec: vec![],
+    };
+    cool_vec.push(1);
+    assert_eq!(cool_vec.vec, [1]);
+
+    let mut forwarded = ForwardedVec {
+        len: 2,
+        vec: vec![1, 2],
+    };
+    let slice: &mut [i32] = &mut forwarded;
+    slice[0] = 5;
+    assert_eq!(forwarded.vec, [5, 2]);
+}
+
+#[derive(Deref, DerefMut)]
+enum Buffer {
+    Heap(Vec<u8>),
+    Mapped {
+        len: usize,
+        #[deref]
+        data: Vec<u8>,
+    },
+}
+
+#[test]
+fn deref_mut_enum() {
+    let mut heap = Buffer::Heap(vec![]);
+    heap.push(1);
+    assert_eq!(*heap, [1]);
+    let mut mapped = Buffer::Mapped {
+        len: 1,
+        data: vec![2],
+    };
+    mapped[0] = 3;
+    assert_eq!(*mapped, [3]);
+}
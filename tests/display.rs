@@ -129,6 +129,61 @@ fn check_display() {
     );
 }
 
+mod pad {
+    #[derive(Display)]
+    #[display(pad)]
+    #[display(fmt = "({}, {})", x, y)]
+    struct Point2D {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(Display)]
+    #[display(pad)]
+    enum Shape {
+        #[display(fmt = "circle r={}", _0)]
+        Circle(u32),
+        Dot,
+    }
+
+    #[derive(Display, LowerHex)]
+    #[display(pad)]
+    #[lower_hex(pad)]
+    #[display(fmt = "{}ms", _0)]
+    struct Millis(u64);
+
+    #[test]
+    fn honours_width_fill_and_alignment() {
+        let p = Point2D { x: 3, y: 4 };
+        assert_eq!(format!("{}", p), "(3, 4)");
+        assert_eq!(format!("{:10}|", p), "(3, 4)    |");
+        assert_eq!(format!("{:>10}|", p), "    (3, 4)|");
+        assert_eq!(format!("{:*^10}|", p), "**(3, 4)**|");
+        assert_eq!(format!("{:^9}|", p), " (3, 4)  |");
+        assert_eq!(format!("{:3}|", p), "(3, 4)|");
+    }
+
+    #[test]
+    fn honours_precision() {
+        let p = Point2D { x: 3, y: 4 };
+        assert_eq!(format!("{:.3}|", p), "(3,|");
+        assert_eq!(format!("{:>5.3}|", p), "  (3,|");
+        assert_eq!(format!("{:.10}|", p), "(3, 4)|");
+    }
+
+    #[test]
+    fn enums() {
+        assert_eq!(format!("{:>12}|", Shape::Circle(5)), "  circle r=5|");
+        assert_eq!(format!("{:-<5}|", Shape::Dot), "Dot--|");
+    }
+
+    #[test]
+    fn other_traits() {
+        assert_eq!(format!("{:>8}|", Millis(26)), "    26ms|");
+        assert_eq!(format!("{:>4x}|", Millis(26)), "  1a|");
+    }
+}
+
 mod generic {
     #[derive(Display)]
     #[display(fmt = "Generic {}", field)]
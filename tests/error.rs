@@ -0,0 +1,132 @@
+#![allow(dead_code)]
+#[macro_use]
+extern crate derive_more;
+
+use std::error::Error as _;
+use std::{fmt, io};
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "simple error")]
+struct Simple;
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "failed to read {}", path)]
+struct ReadError {
+    path: &'static str,
+    source: io::Error,
+}
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "wrapped")]
+struct Wrapped(#[error(source)] ReadError);
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "io error")]
+struct FromIo {
+    #[error(from)]
+    inner: io::Error,
+}
+
+#[derive(Debug, Error)]
+#[error(transparent)]
+struct Transparent(ReadError);
+
+#[derive(Debug, Display, Error)]
+enum AppError {
+    #[display(fmt = "not found")]
+    NotFound,
+    #[display(fmt = "io failed")]
+    Io(#[error(from)] io::Error),
+    #[display(fmt = "read failed")]
+    Read { source: ReadError, retries: u32 },
+    #[error(transparent)]
+    Other(Wrapped),
+}
+
+#[derive(Debug, Error)]
+#[error(transparent)]
+enum AnyError {
+    Io(#[error(from)] io::Error),
+    Fmt(#[error(from)] fmt::Error),
+}
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "generic")]
+struct Generic<E> {
+    source: E,
+}
+
+fn read_error() -> ReadError {
+    ReadError {
+        path: "/etc/passwd",
+        source: io::Error::new(io::ErrorKind::NotFound, "no such file"),
+    }
+}
+
+#[test]
+fn struct_sources() {
+    assert!(Simple.source().is_none());
+
+    let err = read_error();
+    assert_eq!(err.source().unwrap().to_string(), "no such file");
+
+    let err = Wrapped(read_error());
+    assert_eq!(
+        err.source().unwrap().to_string(),
+        "failed to read /etc/passwd"
+    );
+
+    let err = FromIo::from(io::Error::new(io::ErrorKind::PermissionDenied, "oh no"));
+    assert_eq!(err.to_string(), "io error");
+    assert_eq!(err.source().unwrap().to_string(), "oh no");
+}
+
+#[test]
+fn transparent() {
+    let err = Transparent(read_error());
+    assert_eq!(err.to_string(), "failed to read /etc/passwd");
+    assert_eq!(err.source().unwrap().to_string(), "no such file");
+
+    let err: AnyError = fmt::Error.into();
+    assert_eq!(
+        err.to_string(),
+        "an error occurred when formatting an argument"
+    );
+    assert!(err.source().is_none());
+}
+
+#[test]
+fn enum_sources() {
+    assert!(AppError::NotFound.source().is_none());
+
+    let err: AppError = io::Error::new(io::ErrorKind::PermissionDenied, "oh no").into();
+    assert_eq!(err.to_string(), "io failed");
+    assert_eq!(err.source().unwrap().to_string(), "oh no");
+
+    let err = AppError::Read {
+        source: read_error(),
+        retries: 3,
+    };
+    assert_eq!(
+        err.source().unwrap().to_string(),
+        "failed to read /etc/passwd"
+    );
+
+    let err = AppError::Other(Wrapped(read_error()));
+    assert_eq!(err.to_string(), "wrapped");
+    assert_eq!(
+        err.source().unwrap().to_string(),
+        "failed to read /etc/passwd"
+    );
+}
+
+#[test]
+fn generic() {
+    let err = Generic {
+        source: read_error(),
+    };
+    assert_eq!(
+        err.source().unwrap().to_string(),
+        "failed to read /etc/passwd"
+    );
+}
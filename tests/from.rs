@@ -37,3 +37,23 @@ enum MixedInts {
     Unsigned(u32),
     NamedUnsigned { x: u32 },
 }
+
+#[derive(Debug, PartialEq, From)]
+#[from(array)]
+struct Color {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+#[derive(Debug, PartialEq, From)]
+#[from(array)]
+struct Names(String, String);
+
+#[test]
+fn from_array() {
+    assert_eq!(Color::from([1, 2, 3]), Color { r: 1, g: 2, b: 3 });
+    assert_eq!(Color::from((1, 2, 3)), Color { r: 1, g: 2, b: 3 });
+    let names: Names = ["a".to_owned(), "b".to_owned()].into();
+    assert_eq!(names, Names("a".to_owned(), "b".to_owned()));
+}
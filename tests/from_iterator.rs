@@ -0,0 +1,115 @@
+#![allow(dead_code)]
+#[macro_use]
+extern crate derive_more;
+
+use std::collections::{BTreeMap, HashSet};
+
+#[derive(FromIterator, Extend, Debug, PartialEq)]
+struct MyVec(Vec<i32>);
+
+#[derive(FromIterator, Extend)]
+struct Scores {
+    scores: BTreeMap<String, u32>,
+}
+
+#[derive(FromIterator, Extend)]
+struct Text(String);
+
+#[derive(FromIterator, Extend)]
+struct Set<T: ::core::hash::Hash + Eq>(HashSet<T>);
+
+#[test]
+fn forwarded() {
+    let mut my_vec: MyVec = (1..3).collect();
+    assert_eq!(my_vec, MyVec(vec![1, 2]));
+    my_vec.extend(vec![3, 4]);
+    assert_eq!(my_vec.0, [1, 2, 3, 4]);
+    my_vec.extend(&[5]);
+    assert_eq!(my_vec.0, [1, 2, 3, 4, 5]);
+
+    let mut scores: Scores = vec![("a".to_string(), 1)].into_iter().collect();
+    scores.extend(vec![("b".to_string(), 2)]);
+    assert_eq!(scores.scores.len(), 2);
+
+    let mut text: Text = vec!["ab", "c"].into_iter().collect();
+    text.extend(vec!['d', 'e']);
+    assert_eq!(text.0, "abcde");
+
+    let set: Set<_> = vec![1, 1, 2].into_iter().collect();
+    assert_eq!(set.0.len(), 2);
+}
+
+#[derive(Debug, PartialEq)]
+struct Item(String);
+
+impl From<&str> for Item {
+    fn from(name: &str) -> Item {
+        Item(name.to_string())
+    }
+}
+
+impl From<u32> for Item {
+    fn from(number: u32) -> Item {
+        Item(number.to_string())
+    }
+}
+
+#[derive(FromIterator, Extend)]
+#[from_iterator(types(Item, u32))]
+struct Batch {
+    id: u64,
+    #[from_iterator(types("&str"))]
+    items: Vec<Item>,
+}
+
+#[test]
+fn converted_types() {
+    let mut batch: Batch = vec!["a", "b"].into_iter().collect();
+    assert_eq!(batch.id, 0);
+    batch.extend(vec![3]);
+    batch.extend(vec![Item("d".to_string())]);
+    assert_eq!(
+        batch.items,
+        [
+            Item("a".to_string()),
+            Item("b".to_string()),
+            Item("3".to_string()),
+            Item("d".to_string()),
+        ]
+    );
+    let batch: Batch = vec![Item("e".to_string())].into_iter().collect();
+    assert_eq!(batch.items, [Item("e".to_string())]);
+}
+
+#[derive(FromIterator, Extend)]
+#[from_iterator(types(char, String))]
+struct Names(Vec<String>);
+
+struct Score(&'static str, u32);
+
+impl From<Score> for (String, u32) {
+    fn from(score: Score) -> (String, u32) {
+        (score.0.to_string(), score.1)
+    }
+}
+
+#[derive(FromIterator)]
+#[from_iterator(types(Score, "(String, u32)"))]
+struct Table(BTreeMap<String, u32>);
+
+#[test]
+fn own_item_type() {
+    let mut names: Names = vec!['a'].into_iter().collect();
+    names.extend(vec!["b".to_string()]);
+    assert_eq!(names.0, ["a", "b"]);
+    let table: Table = vec![Score("a", 1)].into_iter().collect();
+    let table: Table = table
+        .0
+        .into_iter()
+        .chain(vec![("b".to_string(), 2)])
+        .collect();
+    assert_eq!(
+        table.0.into_iter().collect::<Vec<_>>(),
+        [("a".to_string(), 1), ("b".to_string(), 2)]
+    );
+}
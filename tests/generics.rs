@@ -13,16 +13,16 @@ extern crate derive_more;
     Sum,
     IndexMut,
     AddAssign,
-    DerefToInner,
-    DerefMutToInner,
+    Deref,
+    DerefMut,
     IntoIterator,
-    IntoIteratorRef,
-    IntoIteratorRefMut,
     Constructor
 )]
+#[into_iterator(owned, ref, ref_mut)]
 struct Wrapped<T: Clone>(T);
 
 #[derive(Iterator, Deref, DerefMut)]
+#[deref(forward)]
 struct Wrapped2<T: Clone>(T);
 
 #[derive(From, Not, Add, Mul, AddAssign, Constructor, Sum)]
@@ -38,19 +38,19 @@ struct WrappedDouble<T: Clone, U: Clone>(T, U);
     Mul,
     IndexMut,
     AddAssign,
-    DerefToInner,
-    DerefMutToInner,
+    Deref,
+    DerefMut,
     IntoIterator,
-    IntoIteratorRef,
-    IntoIteratorRefMut,
     Constructor,
     Sum
 )]
+#[into_iterator(owned, ref, ref_mut)]
 struct Struct<T: Clone> {
     t: T,
 }
 
 #[derive(Iterator, Deref, DerefMut)]
+#[deref(forward)]
 struct Struct2<T: Clone> {
     t: T,
 }
@@ -0,0 +1,113 @@
+#![allow(dead_code, unused_imports)]
+#[macro_use]
+extern crate derive_more;
+
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+#[derive(Getters)]
+struct Config {
+    port: u16,
+    name: String,
+    ratio: (f32, f64),
+    label: &'static str,
+    #[getter(skip)]
+    secret: String,
+}
+
+#[derive(Getters)]
+struct Modes {
+    #[getter(clone)]
+    shared: Rc<String>,
+    #[getter(deref)]
+    path: PathBuf,
+    #[getter(ref)]
+    count: u64,
+    #[getter(copy)]
+    id: Id,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Id(u32);
+
+#[derive(Getters)]
+#[getter(mut, vis = "pub(crate)")]
+struct Counter<T> {
+    value: T,
+    #[getter(vis = "pub")]
+    steps: usize,
+    r#type: &'static str,
+}
+
+mod private {
+    #[derive(Getters)]
+    pub struct Entity {
+        id: u64,
+        #[getter(mut)]
+        tags: Vec<String>,
+    }
+
+    impl Entity {
+        pub fn new() -> Self {
+            Entity {
+                id: 1,
+                tags: vec![],
+            }
+        }
+    }
+}
+
+#[test]
+fn by_type() {
+    let config = Config {
+        port: 80,
+        name: "server".to_owned(),
+        ratio: (0.5, 0.25),
+        label: "label",
+        secret: String::new(),
+    };
+    let port: u16 = config.port();
+    assert_eq!(80, port);
+    let name: &String = config.name();
+    assert_eq!("server", name);
+    assert_eq!((0.5, 0.25), config.ratio());
+    let label: &'static str = config.label();
+    assert_eq!("label", label);
+}
+
+#[test]
+fn modes() {
+    let modes = Modes {
+        shared: Rc::new("shared".to_owned()),
+        path: PathBuf::from("/tmp"),
+        count: 3,
+        id: Id(7),
+    };
+    let shared: Rc<String> = modes.shared();
+    assert_eq!(2, Rc::strong_count(&shared));
+    let path: &Path = modes.path();
+    assert_eq!(Path::new("/tmp"), path);
+    let count: &u64 = modes.count();
+    assert_eq!(3, *count);
+    assert_eq!(Id(7), modes.id());
+}
+
+#[test]
+fn mutable() {
+    let mut counter = Counter {
+        value: vec![1],
+        steps: 0,
+        r#type: "counter",
+    };
+    counter.value_mut().push(2);
+    *counter.steps_mut() += 1;
+    *counter.type_mut() = "other";
+    assert_eq!(&vec![1, 2], counter.value());
+    assert_eq!(1, counter.steps());
+    assert_eq!("other", counter.r#type());
+
+    let mut entity = private::Entity::new();
+    entity.tags_mut().push("a".to_owned());
+    assert_eq!(1, entity.id());
+    assert_eq!(&["a".to_owned()], &entity.tags()[..]);
+}
@@ -9,3 +9,82 @@ struct MyVec(Vec<i32>);
 struct Numbers {
     numbers: Vec<i32>,
 }
+
+#[derive(Index)]
+struct Buffer {
+    #[index]
+    data: Vec<u8>,
+    len: usize,
+    capacity: usize,
+}
+
+#[derive(Index)]
+struct Labeled<'a>(&'a str, #[index] [i32; 3]);
+
+#[test]
+fn marked_field() {
+    let buffer = Buffer {
+        data: vec![1, 2, 3],
+        len: 3,
+        capacity: 3,
+    };
+    assert_eq!(buffer[1], 2);
+    assert_eq!(&buffer[1..], &[2, 3]);
+    assert_eq!(Labeled("label", [4, 5, 6])[2], 6);
+}
+
+#[derive(Index)]
+#[index(fields)]
+struct Vec3 {
+    x: f32,
+    y: f32,
+    z: f32,
+}
+
+#[derive(Index)]
+#[index(fields)]
+struct Pair<T>(T, T);
+
+#[test]
+fn positional() {
+    let v = Vec3 {
+        x: 1.0,
+        y: 2.0,
+        z: 3.0,
+    };
+    assert_eq!(v[0], 1.0);
+    assert_eq!(v[1], 2.0);
+    assert_eq!(v[2], 3.0);
+    assert_eq!(v.iter().copied().collect::<Vec<_>>(), [1.0, 2.0, 3.0]);
+    assert_eq!(v.iter().len(), 3);
+
+    let pair = Pair("a", "b");
+    assert_eq!(pair[1], "b");
+    assert_eq!(pair.iter().collect::<Vec<_>>(), [&"a", &"b"]);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+fn positional_out_of_bounds() {
+    let v = Vec3 {
+        x: 1.0,
+        y: 2.0,
+        z: 3.0,
+    };
+    let _ = v[3];
+}
+
+#[derive(Index)]
+enum Storage<'a> {
+    Owned(Vec<i32>),
+    Shared(&'a str, #[index] Vec<i32>),
+}
+
+#[test]
+fn enum_variants() {
+    let owned = Storage::Owned(vec![1, 2]);
+    assert_eq!(owned[1], 2);
+    let shared = Storage::Shared("shared", vec![3, 4]);
+    assert_eq!(shared[0], 3);
+    assert_eq!(shared[..], [3, 4]);
+}
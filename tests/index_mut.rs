@@ -32,3 +32,73 @@ where
         <Vec<i32> as ::core::ops::Index<__IdxT>>::index(&self.numbers, idx)
     }
 }
+
+#[derive(Index, IndexMut)]
+struct Buffer {
+    #[index]
+    data: Vec<u8>,
+    len: usize,
+}
+
+#[test]
+fn marked_field() {
+    let mut buffer = Buffer {
+        data: vec![1, 2, 3],
+        len: 3,
+    };
+    buffer[1] = 4;
+    assert_eq!(buffer.data, [1, 4, 3]);
+    assert_eq!(buffer[1], 4);
+}
+
+#[derive(Debug, PartialEq, Index, IndexMut)]
+#[index(fields)]
+struct Vec3 {
+    x: f32,
+    y: f32,
+    z: f32,
+}
+
+#[test]
+fn positional() {
+    let mut v = Vec3 {
+        x: 1.0,
+        y: 2.0,
+        z: 3.0,
+    };
+    v[0] = 4.0;
+    for field in v.iter_mut() {
+        *field *= 2.0;
+    }
+    assert_eq!(
+        v,
+        Vec3 {
+            x: 8.0,
+            y: 4.0,
+            z: 6.0,
+        }
+    );
+}
+
+#[derive(Index, IndexMut)]
+enum Storage {
+    Heap(Vec<i32>),
+    Mapped {
+        offset: usize,
+        #[index]
+        data: Vec<i32>,
+    },
+}
+
+#[test]
+fn enum_variants() {
+    let mut heap = Storage::Heap(vec![1, 2]);
+    heap[1] = 3;
+    assert_eq!(heap[..], [1, 3]);
+    let mut mapped = Storage::Mapped {
+        offset: 0,
+        data: vec![4],
+    };
+    mapped[0] += 1;
+    assert_eq!(mapped[0], 5);
+}
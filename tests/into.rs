@@ -27,3 +27,28 @@ struct Point2D {
     x: i32,
     y: i32,
 }
+
+#[derive(Into, IntoRef, IntoRefMut)]
+#[into(array)]
+struct Color {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+#[derive(Into)]
+#[into(array)]
+struct Pair<T>(T, T);
+
+#[test]
+fn into_array() {
+    let mut color = Color { r: 1, g: 2, b: 3 };
+    for c in <[&mut u8; 3]>::from(&mut color) {
+        *c += 1;
+    }
+    assert_eq!(<[&u8; 3]>::from(&color), [&2, &3, &4]);
+    let rgb: [u8; 3] = color.into();
+    assert_eq!(rgb, [2, 3, 4]);
+    let pair: [String; 2] = Pair("a".to_owned(), "b".to_owned()).into();
+    assert_eq!(pair, ["a", "b"]);
+}
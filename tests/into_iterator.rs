@@ -2,10 +2,82 @@
 #[macro_use]
 extern crate derive_more;
 
-#[derive(IntoIterator, IntoIteratorRef)]
+#[derive(IntoIterator)]
+#[into_iterator(owned, ref)]
 struct MyVec(Vec<i32>);
 
-#[derive(IntoIterator, IntoIteratorRef)]
+#[derive(IntoIterator)]
+#[into_iterator(owned, ref)]
 struct Numbers {
     numbers: Vec<i32>,
 }
+
+#[derive(IntoIterator)]
+struct Batch {
+    id: u64,
+    #[into_iterator(owned, ref, ref_mut)]
+    items: Vec<String>,
+}
+
+#[derive(IntoIterator)]
+struct Borrowed<'a, T> {
+    #[into_iterator]
+    items: &'a [T],
+    len: usize,
+}
+
+#[test]
+fn selected_field() {
+    let mut batch = Batch {
+        id: 1,
+        items: vec!["a".to_string(), "b".to_string()],
+    };
+    for item in &mut batch {
+        item.push('!');
+    }
+    assert_eq!((&batch).into_iter().collect::<Vec<_>>(), ["a!", "b!"]);
+    assert_eq!(batch.into_iter().collect::<Vec<_>>(), ["a!", "b!"]);
+
+    let borrowed = Borrowed {
+        items: &[1, 2],
+        len: 2,
+    };
+    assert_eq!(borrowed.into_iter().collect::<Vec<_>>(), [&1, &2]);
+}
+
+#[derive(IntoIterator)]
+#[into_iterator(owned, ref, ref_mut)]
+enum Items {
+    Few(Vec<i32>),
+    Many {
+        total: usize,
+        #[into_iterator]
+        items: ::std::collections::VecDeque<i32>,
+    },
+}
+
+#[test]
+fn enum_into_iterator() {
+    let few = Items::Few(vec![1, 2]);
+    assert_eq!(few.into_iter().collect::<Vec<_>>(), [1, 2]);
+
+    let mut many = Items::Many {
+        total: 3,
+        items: vec![3, 4, 5].into_iter().collect(),
+    };
+    assert_eq!((&many).into_iter().collect::<Vec<_>>(), [&3, &4, &5]);
+    for item in &mut many {
+        *item += 1;
+    }
+    let mut iter = many.into_iter();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next_back(), Some(6));
+    assert_eq!(iter.collect::<Vec<_>>(), [4, 5]);
+}
+
+fn assert_fused<I: ::core::iter::FusedIterator>(_: &I) {}
+
+#[test]
+fn enum_into_iterator_fused() {
+    assert_fused(&Items::Few(vec![1]).into_iter());
+}
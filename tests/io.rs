@@ -0,0 +1,161 @@
+#![allow(dead_code, unused_imports)]
+#[macro_use]
+extern crate derive_more;
+
+use std::fmt::Write as _;
+use std::io::{BufRead, Cursor, IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write};
+
+#[derive(Read, BufRead, Seek)]
+struct MyCursor(Cursor<Vec<u8>>);
+
+#[derive(Write)]
+struct CountingWriter {
+    count: usize,
+    #[io]
+    inner: Vec<u8>,
+}
+
+#[derive(Read, BufRead)]
+enum Input<'a> {
+    Memory(Cursor<Vec<u8>>),
+    Slice {
+        name: &'a str,
+        #[io]
+        data: &'a [u8],
+    },
+}
+
+#[derive(Write)]
+struct Generic<W>(W);
+
+#[derive(FmtWrite)]
+struct Builder(String);
+
+#[derive(FmtWrite)]
+enum Sink {
+    Text(String),
+    Named {
+        name: &'static str,
+        #[fmt_write]
+        text: String,
+    },
+}
+
+#[test]
+fn read() {
+    let mut cursor = MyCursor(Cursor::new(b"hello world".to_vec()));
+    let mut buf = [0; 5];
+    cursor.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"hello");
+    let mut rest = String::new();
+    assert_eq!(cursor.read_to_string(&mut rest).unwrap(), 6);
+    assert_eq!(rest, " world");
+}
+
+#[test]
+fn read_vectored() {
+    let mut cursor = MyCursor(Cursor::new(b"abcdef".to_vec()));
+    let (mut first, mut second) = ([0; 2], [0; 4]);
+    let mut bufs = [IoSliceMut::new(&mut first), IoSliceMut::new(&mut second)];
+    assert_eq!(cursor.read_vectored(&mut bufs).unwrap(), 6);
+    assert_eq!(&first, b"ab");
+    assert_eq!(&second, b"cdef");
+}
+
+#[test]
+fn buf_read() {
+    let mut cursor = MyCursor(Cursor::new(b"first\nsecond\n".to_vec()));
+    let mut line = String::new();
+    cursor.read_line(&mut line).unwrap();
+    assert_eq!(line, "first\n");
+    assert_eq!(cursor.fill_buf().unwrap(), b"second\n");
+    cursor.consume(3);
+    let mut rest = vec![];
+    cursor.read_until(b'\n', &mut rest).unwrap();
+    assert_eq!(rest, b"ond\n");
+}
+
+#[test]
+fn seek() {
+    let mut cursor = MyCursor(Cursor::new(b"hello".to_vec()));
+    assert_eq!(cursor.seek(SeekFrom::End(-2)).unwrap(), 3);
+    assert_eq!(cursor.stream_position().unwrap(), 3);
+    let mut rest = vec![];
+    cursor.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, b"lo");
+    cursor.rewind().unwrap();
+    assert_eq!(cursor.stream_position().unwrap(), 0);
+}
+
+#[cfg(derive_more_seek_relative)]
+#[test]
+fn seek_relative() {
+    let mut cursor = MyCursor(Cursor::new(b"hello".to_vec()));
+    cursor.seek_relative(3).unwrap();
+    cursor.seek_relative(-1).unwrap();
+    assert_eq!(cursor.stream_position().unwrap(), 2);
+}
+
+#[cfg(derive_more_skip_until)]
+#[test]
+fn skip_until() {
+    let mut cursor = MyCursor(Cursor::new(b"first\nsecond".to_vec()));
+    assert_eq!(cursor.skip_until(b'\n').unwrap(), 6);
+    let mut rest = String::new();
+    cursor.read_to_string(&mut rest).unwrap();
+    assert_eq!(rest, "second");
+}
+
+#[test]
+fn write() {
+    let mut writer = CountingWriter {
+        count: 0,
+        inner: vec![],
+    };
+    writer.write_all(b"abc").unwrap();
+    let bufs = [IoSlice::new(b"de"), IoSlice::new(b"f")];
+    assert_eq!(writer.write_vectored(&bufs).unwrap(), 3);
+    write!(writer, "{}", 42).unwrap();
+    writer.flush().unwrap();
+    assert_eq!(writer.inner, b"abcdef42");
+    assert_eq!(writer.count, 0);
+
+    let mut generic = Generic(vec![]);
+    generic.write_all(b"generic").unwrap();
+    assert_eq!(generic.0, b"generic");
+}
+
+#[test]
+fn enum_read() {
+    let mut memory = Input::Memory(Cursor::new(b"memory\n".to_vec()));
+    let mut line = String::new();
+    memory.read_line(&mut line).unwrap();
+    assert_eq!(line, "memory\n");
+
+    let mut slice = Input::Slice {
+        name: "slice",
+        data: b"slice",
+    };
+    let mut buf = vec![];
+    slice.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, b"slice");
+}
+
+#[test]
+fn fmt_write() {
+    let mut builder = Builder(String::new());
+    builder.write_str("abc").unwrap();
+    builder.write_char('d').unwrap();
+    write!(builder, "{}", 1).unwrap();
+    assert_eq!(builder.0, "abcd1");
+
+    let mut sink = Sink::Named {
+        name: "sink",
+        text: String::new(),
+    };
+    write!(sink, "{}-{}", 1, 2).unwrap();
+    match sink {
+        Sink::Named { text, .. } => assert_eq!(text, "1-2"),
+        Sink::Text(_) => unreachable!(),
+    }
+}
@@ -0,0 +1,70 @@
+#![allow(dead_code)]
+#[macro_use]
+extern crate derive_more;
+
+#[derive(IsVariant)]
+enum Either<TLeft, TRight> {
+    Left(TLeft),
+    Right(TRight),
+}
+
+#[derive(IsVariant)]
+enum Maybe<T> {
+    Nothing,
+    Just(T),
+}
+
+#[derive(IsVariant)]
+enum Color {
+    Rgb(u8, u8, u8),
+    Cmyk {
+        c: u8,
+        m: u8,
+        y: u8,
+        k: u8,
+    },
+    HTTPColorName(String),
+    #[is_variant(rename = "transparent")]
+    Clear,
+    #[is_variant(ignore)]
+    Unknown,
+}
+
+#[derive(IsVariant)]
+enum Single {
+    Only,
+}
+
+#[derive(IsVariant)]
+enum Empty {}
+
+const NOTHING: [bool; 2] = [
+    Maybe::<u8>::Nothing.is_nothing(),
+    Maybe::<u8>::Nothing.is_just(),
+];
+
+#[test]
+fn generic() {
+    assert!(Either::<u8, ()>::Left(1).is_left());
+    assert!(!Either::<u8, ()>::Left(1).is_right());
+    assert!(Either::<(), u8>::Right(1).is_right());
+    assert!(Maybe::Just(String::new()).is_just());
+    assert!(!Maybe::Just(String::new()).is_nothing());
+    assert_eq!(NOTHING, [true, false]);
+}
+
+#[test]
+fn snake_case_and_attributes() {
+    assert!(Color::Rgb(0, 0, 0).is_rgb());
+    assert!(Color::Cmyk {
+        c: 0,
+        m: 0,
+        y: 0,
+        k: 0
+    }
+    .is_cmyk());
+    assert!(Color::HTTPColorName("red".into()).is_http_color_name());
+    assert!(Color::Clear.is_transparent());
+    assert!(!Color::Unknown.is_rgb());
+    assert!(Single::Only.is_only());
+}
@@ -9,3 +9,87 @@ struct MyVec<'a>(::core::slice::Iter<'a, i32>);
 struct Numbers<'a> {
     numbers: ::core::slice::Iter<'a, i32>,
 }
+
+#[derive(Iterator, DoubleEndedIterator, ExactSizeIterator)]
+enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+fn evens_or_odds(even: bool) -> Either<impl Iterator<Item = u32>, impl Iterator<Item = u32>> {
+    if even {
+        Either::Left((0..10).step_by(2))
+    } else {
+        Either::Right((0..10).filter(|n| n % 2 == 1))
+    }
+}
+
+#[derive(Iterator)]
+enum Chars<'a> {
+    Forward(::core::str::Chars<'a>),
+    Backward {
+        skipped: usize,
+        #[iterator]
+        chars: ::core::iter::Rev<::core::str::Chars<'a>>,
+    },
+}
+
+#[test]
+fn enum_iterator() {
+    assert_eq!(evens_or_odds(true).collect::<Vec<_>>(), [0, 2, 4, 6, 8]);
+    assert_eq!(evens_or_odds(false).collect::<Vec<_>>(), [1, 3, 5, 7, 9]);
+
+    let left: Either<_, ::std::vec::IntoIter<i32>> = Either::Left(vec![1, 2, 3].into_iter());
+    assert_eq!(left.size_hint(), (3, Some(3)));
+    assert_eq!(left.len(), 3);
+    assert_eq!(left.rev().collect::<Vec<_>>(), [3, 2, 1]);
+
+    let chars = Chars::Backward {
+        skipped: 0,
+        chars: "abc".chars().rev(),
+    };
+    assert_eq!(chars.collect::<String>(), "cba");
+    assert_eq!(Chars::Forward("abc".chars()).collect::<String>(), "abc");
+}
+
+#[derive(Iterator, DoubleEndedIterator, ExactSizeIterator, FusedIterator)]
+struct Wrapper<I>(I);
+
+fn assert_fused<I: ::core::iter::FusedIterator>(_: &I) {}
+
+#[test]
+fn forwarded_methods() {
+    let mut iter = Wrapper(vec![1, 2, 3, 4, 5].into_iter());
+    assert_eq!(iter.size_hint(), (5, Some(5)));
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.nth(1), Some(2));
+    assert_eq!(iter.nth_back(1), Some(4));
+    assert_fused(&iter);
+    assert_eq!(Wrapper(1..4).count(), 3);
+    assert_eq!(Wrapper(1..4).fold(0, |acc, n| acc * 10 + n), 123);
+    assert_eq!(Wrapper(1..4).rfold(0, |acc, n| acc * 10 + n), 321);
+    assert_eq!(Wrapper(1..4).rev().collect::<Vec<_>>(), [3, 2, 1]);
+
+    let mut either: Either<_, ::core::ops::Range<i32>> = Either::Left(vec![1, 2, 3].into_iter());
+    assert_eq!(either.nth(1), Some(2));
+    assert_eq!(either.count(), 1);
+}
+
+#[derive(Iterator, DoubleEndedIterator)]
+struct Counted {
+    inner: ::std::vec::IntoIter<u32>,
+}
+
+impl Drop for Counted {
+    fn drop(&mut self) {}
+}
+
+#[test]
+fn drop_wrapper() {
+    let counted = || Counted {
+        inner: vec![1, 2, 3].into_iter(),
+    };
+    assert_eq!(counted().count(), 3);
+    assert_eq!(counted().fold(0, |acc, n| acc * 10 + n), 123);
+    assert_eq!(counted().rfold(0, |acc, n| acc * 10 + n), 321);
+}
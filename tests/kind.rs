@@ -0,0 +1,63 @@
+#![allow(dead_code)]
+#[macro_use]
+extern crate derive_more;
+
+use std::collections::HashMap;
+
+#[derive(Kind)]
+#[kind(derive(Hash, PartialOrd, Ord))]
+enum Event {
+    /// A key was pressed
+    KeyPress(char),
+    Click {
+        x: i32,
+        y: i32,
+    },
+    Quit,
+}
+
+#[derive(Kind)]
+#[kind(name = "ValueType")]
+pub enum Value<'a, T: Clone> {
+    Borrowed(&'a T),
+    Owned(T),
+}
+
+#[derive(Kind)]
+enum Empty {}
+
+#[derive(Kind)]
+#[kind(derive(Debug, Hash, ::core::clone::Clone))]
+enum Repeated {
+    A,
+    B(u8),
+}
+
+#[test]
+fn kind() {
+    assert_eq!(Event::KeyPress('a').kind(), EventKind::KeyPress);
+    assert_eq!(Event::Click { x: 1, y: 2 }.kind(), EventKind::Click);
+    assert_eq!(Event::Quit.kind(), EventKind::Quit);
+    assert_eq!(Value::Owned(1).kind(), ValueType::Owned);
+    assert_eq!(Value::Borrowed(&1).kind(), ValueType::Borrowed);
+}
+
+#[test]
+fn from_ref() {
+    let event = Event::Click { x: 1, y: 2 };
+    assert_eq!(EventKind::from(&event), EventKind::Click);
+    let kind: ValueType = (&Value::Owned(1)).into();
+    assert_eq!(kind, ValueType::Owned);
+}
+
+#[test]
+fn extra_derives() {
+    let mut counts = HashMap::new();
+    for event in &[Event::KeyPress('a'), Event::Quit, Event::KeyPress('b')] {
+        *counts.entry(event.kind()).or_insert(0) += 1;
+    }
+    assert_eq!(counts[&EventKind::KeyPress], 2);
+    assert_eq!(counts[&EventKind::Quit], 1);
+    assert!(EventKind::KeyPress < EventKind::Quit);
+    assert_eq!(format!("{:?}", EventKind::Click), "Click");
+}
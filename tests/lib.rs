@@ -16,7 +16,7 @@ extern crate derive_more;
 #[derive(Display)]
 #[derive(Octal)]
 #[derive(Binary)]
-#[derive(DerefToInner, DerefMutToInner)]
+#[derive(Deref, DerefMut)]
 #[derive(Sum)]
 #[derive(MulSelf)]
 #[derive(Product)]
@@ -25,7 +25,9 @@ struct MyInt(i32);
 #[derive(Eq, PartialEq, Debug)]
 #[derive(Index, IndexMut)]
 #[derive(Deref, DerefMut)]
-#[derive(IntoIterator, IntoIteratorRef, IntoIteratorRefMut)]
+#[derive(IntoIterator)]
+#[into_iterator(owned, ref, ref_mut)]
+#[deref(forward)]
 struct MyVec(Vec<i32>);
 
 #[derive(Iterator)]
@@ -33,6 +35,7 @@ struct MyIter<'a>(::core::slice::Iter<'a, i32>);
 
 #[derive(Eq, PartialEq, Debug)]
 #[derive(Deref, DerefMut)]
+#[deref(forward)]
 struct MyBoxedInt(Box<i32>);
 
 #[derive(Eq, PartialEq, Debug)]
@@ -18,3 +18,23 @@ struct Point2D {
     x: i32,
     y: i32,
 }
+
+#[derive(Mul)]
+struct Scaled {
+    factor: i32,
+    #[mul(skip)]
+    id: String,
+}
+
+#[derive(Mul)]
+struct Meters<Unit>(i32, #[mul(skip)] ::std::marker::PhantomData<Unit>);
+
+#[test]
+fn mul_skipped_fields() {
+    let scaled = Scaled {
+        factor: 2,
+        id: "a".to_owned(),
+    } * 3;
+    assert_eq!(6, scaled.factor);
+    assert_eq!("a", scaled.id);
+}
@@ -18,3 +18,23 @@ struct Point2D {
     x: i32,
     y: i32,
 }
+
+#[derive(MulAssign)]
+struct Scaled {
+    x: i32,
+    y: i32,
+    #[mul(skip)]
+    id: String,
+}
+
+#[test]
+fn mul_assign_skipped_fields() {
+    let mut scaled = Scaled {
+        x: 1,
+        y: 2,
+        id: "a".to_owned(),
+    };
+    scaled *= 3;
+    assert_eq!((3, 6), (scaled.x, scaled.y));
+    assert_eq!("a", scaled.id);
+}
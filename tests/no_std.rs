@@ -19,8 +19,8 @@ extern crate derive_more;
     From,
     IndexMut,
     Sum,
-    DerefToInner,
-    DerefMutToInner,
+    Deref,
+    DerefMut,
     Constructor
 )]
 struct MyInts(u64);
@@ -39,16 +39,16 @@ struct MyVec<'a>(::core::slice::Iter<'a, i32>);
     Sum,
     IndexMut,
     AddAssign,
-    DerefToInner,
-    DerefMutToInner,
+    Deref,
+    DerefMut,
     IntoIterator,
-    IntoIteratorRef,
-    IntoIteratorRefMut,
     Constructor
 )]
+#[into_iterator(owned, ref, ref_mut)]
 struct Wrapped<T: Clone>(T);
 
 #[derive(Iterator, Deref, DerefMut)]
+#[deref(forward)]
 struct Wrapped2<T: Clone>(T);
 
 #[derive(From, Not, Add, Mul, AddAssign, Constructor, Sum)]
@@ -69,3 +69,8 @@ enum EnumWithUnit {
     SmallInt(i32),
     Unit,
 }
+
+#[derive(Display)]
+#[display(pad)]
+#[display(fmt = "{}, {}", _0, _1)]
+struct PaddedPair(u8, u8);
@@ -26,3 +26,38 @@ enum EnumWithUnit {
     SmallInt(i32),
     Unit,
 }
+
+#[derive(Not, Neg, Debug, PartialEq)]
+struct Flags<Unit> {
+    bits: i8,
+    #[not(skip)]
+    unit: ::std::marker::PhantomData<Unit>,
+}
+
+#[derive(Not, Debug, PartialEq)]
+enum MixedSkipped {
+    Tagged(#[not(skip)] &'static str, bool),
+    Named {
+        #[not(skip)]
+        id: u64,
+        set: bool,
+    },
+    Unit,
+}
+
+#[test]
+fn not_skipped_fields() {
+    let flags: Flags<()> = Flags {
+        bits: 1,
+        unit: ::std::marker::PhantomData,
+    };
+    assert_eq!(-1, (-flags).bits);
+    assert_eq!(
+        Ok(MixedSkipped::Tagged("a", false)),
+        !MixedSkipped::Tagged("a", true)
+    );
+    assert_eq!(
+        Ok(MixedSkipped::Named { id: 1, set: true }),
+        !MixedSkipped::Named { id: 1, set: false }
+    );
+}
@@ -0,0 +1,80 @@
+#![allow(dead_code, unused_imports)]
+#[macro_use]
+extern crate derive_more;
+
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+#[derive(PartialEq, PartialEqInner, PartialOrd, PartialOrdInner, Debug)]
+struct UserId(u64);
+
+#[derive(PartialEq, PartialEqInner, PartialOrd, PartialOrdInner, Debug)]
+struct Name {
+    #[cmp_inner]
+    name: String,
+}
+
+#[derive(PartialEqInner, PartialOrdInner)]
+struct Id<T> {
+    id: u64,
+    #[cmp_inner]
+    marker: PhantomData<T>,
+}
+
+#[derive(PartialEqInner, PartialOrdInner)]
+struct Wrapper<T>(T);
+
+#[derive(PartialEqInner)]
+struct Items<T>(Vec<T>);
+
+#[derive(PartialEqInner, PartialOrdInner)]
+struct Borrowed<'a, T>(&'a T);
+
+#[derive(PartialEqInner)]
+struct Boxed<T>(Box<T>);
+
+#[test]
+fn partial_eq() {
+    let user_id = UserId(42);
+    assert_eq!(user_id, 42);
+    assert_eq!(42, user_id);
+    assert_ne!(user_id, 43);
+
+    let name = Name {
+        name: "root".to_owned(),
+    };
+    assert!(name == "root");
+    assert!("root" == name);
+    assert!(name == *"root");
+    assert!(*"root" == name);
+    let root = "root".to_owned();
+    assert!(name == root);
+    assert!(root == name);
+    assert!(name != "user");
+
+    assert!(Wrapper(1) == 1);
+    assert!(Items(vec![1, 2]) == vec![1, 2]);
+    assert!(vec![1, 2] == Items(vec![1, 2]));
+    assert!(Borrowed(&1) == &1);
+    assert!(Boxed(Box::new(1)) == Box::new(1));
+}
+
+#[test]
+fn partial_ord() {
+    let user_id = UserId(42);
+    assert!(user_id < 43);
+    assert!(41 < user_id);
+    assert_eq!(Some(Ordering::Equal), user_id.partial_cmp(&42));
+
+    let name = Name {
+        name: "b".to_owned(),
+    };
+    assert!(name > "a");
+    assert!("c" > name);
+    assert!(*"a" < name);
+    let c = "c".to_owned();
+    assert!(name < c);
+
+    assert!(Wrapper(1.0) < 2.0);
+    assert!(Borrowed(&1.0) < &2.0);
+}
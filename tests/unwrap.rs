@@ -0,0 +1,72 @@
+#![allow(dead_code)]
+#[macro_use]
+extern crate derive_more;
+
+#[derive(Debug, PartialEq, Unwrap, TryUnwrap, AsVariant)]
+enum Either<TLeft, TRight> {
+    Left(TLeft),
+    Right(TRight),
+}
+
+#[derive(Debug, PartialEq, Unwrap, TryUnwrap, AsVariant)]
+enum Shape {
+    Point,
+    Circle(u32),
+    Rect(u32, u32),
+    Line {
+        from: i32,
+        to: i32,
+    },
+    // Shares its field type with `Circle`
+    Square(u32),
+    #[unwrap(rename = "poly")]
+    #[try_unwrap(ignore)]
+    #[as_variant(ignore)]
+    Polygon(Vec<(i32, i32)>),
+}
+
+#[test]
+fn unwrap() {
+    assert_eq!(Either::<u8, ()>::Left(1).unwrap_left(), 1);
+    assert_eq!(Either::<(), u8>::Right(2).unwrap_right(), 2);
+    assert_eq!(Shape::Point.unwrap_point(), ());
+    assert_eq!(Shape::Circle(1).unwrap_circle(), 1);
+    assert_eq!(Shape::Square(2).unwrap_square(), 2);
+    assert_eq!(Shape::Rect(1, 2).unwrap_rect(), (1, 2));
+    assert_eq!(Shape::Line { from: 1, to: 2 }.unwrap_line(), (1, 2));
+    assert_eq!(Shape::Polygon(vec![(0, 0)]).unwrap_poly(), vec![(0, 0)]);
+}
+
+#[test]
+#[should_panic(expected = "called `Shape::unwrap_circle()` on a `Shape::Square` value")]
+fn unwrap_other_variant() {
+    Shape::Square(2).unwrap_circle();
+}
+
+#[test]
+fn try_unwrap() {
+    assert_eq!(Shape::Circle(1).try_unwrap_circle(), Ok(1));
+    assert_eq!(Shape::Square(1).try_unwrap_circle(), Err(Shape::Square(1)));
+    assert_eq!(Shape::Rect(1, 2).try_unwrap_rect(), Ok((1, 2)));
+    assert_eq!(
+        Either::<u8, u8>::Right(1).try_unwrap_left(),
+        Err(Either::Right(1))
+    );
+}
+
+#[test]
+fn as_variant() {
+    let mut shape = Shape::Line { from: 1, to: 2 };
+    assert_eq!(shape.as_line(), Some((&1, &2)));
+    assert_eq!(shape.as_circle(), None);
+    if let Some((from, to)) = shape.as_line_mut() {
+        *from = 3;
+        *to = 4;
+    }
+    assert_eq!(shape, Shape::Line { from: 3, to: 4 });
+
+    let mut either = Either::<String, u8>::Left("left".to_owned());
+    either.as_left_mut().unwrap().push('!');
+    assert_eq!(either.as_left().map(String::as_str), Some("left!"));
+    assert_eq!(either.as_right_mut(), None);
+}
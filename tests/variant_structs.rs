@@ -0,0 +1,112 @@
+#![allow(dead_code)]
+#[macro_use]
+extern crate derive_more;
+
+use std::convert::TryFrom;
+
+#[derive(Debug, PartialEq, VariantStructs)]
+#[variant_structs(derive(Debug, Clone, PartialEq))]
+pub enum Event {
+    /// A mouse click
+    Click {
+        x: i32,
+        y: i32,
+    },
+    KeyPress {
+        key: char,
+    },
+    #[variant_structs(name = "ResizeEvent")]
+    Resize {
+        width: u32,
+        height: u32,
+    },
+    #[variant_structs(ignore)]
+    Scroll {
+        delta: i32,
+    },
+    Focus(bool),
+    Quit,
+}
+
+#[derive(VariantStructs)]
+enum Single {
+    Only { value: String },
+}
+
+#[derive(Debug, PartialEq, VariantStructs)]
+#[variant_structs(derive(Debug, PartialEq))]
+enum Message<'a, 'b: 'a, T: Clone, U>
+where
+    U: Into<T>,
+    T: 'b,
+{
+    Text { body: &'a str },
+    Borrowed { value: &'a T, other: &'b str },
+    Converted { value: U },
+}
+
+fn handle_click(click: Click) -> i32 {
+    click.x + click.y
+}
+
+#[test]
+fn from_struct() {
+    assert_eq!(
+        Event::from(Click { x: 1, y: 2 }),
+        Event::Click { x: 1, y: 2 }
+    );
+    assert_eq!(
+        Event::from(KeyPress { key: 'a' }),
+        Event::KeyPress { key: 'a' }
+    );
+    let event: Event = ResizeEvent {
+        width: 1,
+        height: 2,
+    }
+    .into();
+    assert_eq!(
+        event,
+        Event::Resize {
+            width: 1,
+            height: 2
+        }
+    );
+}
+
+#[test]
+fn try_from_enum() {
+    let click = Click::try_from(Event::Click { x: 1, y: 2 }).unwrap();
+    assert_eq!(click.clone(), Click { x: 1, y: 2 });
+    assert_eq!(handle_click(click), 3);
+    assert_eq!(Click::try_from(Event::Quit), Err(Event::Quit));
+    assert_eq!(
+        KeyPress::try_from(Event::Focus(true)),
+        Err(Event::Focus(true))
+    );
+    let single = Only::try_from(Single::Only {
+        value: "value".to_owned(),
+    });
+    assert_eq!(single.ok().map(|s| s.value), Some("value".to_owned()));
+}
+
+#[test]
+fn generic_enum() {
+    let message: Message<'_, '_, u8, u8> = Message::Text { body: "hi" };
+    let text = Text::try_from(message).unwrap();
+    assert_eq!(text, Text { body: "hi" });
+    let message: Message<'_, '_, u8, u8> = Borrowed {
+        value: &1,
+        other: "other",
+    }
+    .into();
+    assert_eq!(
+        Borrowed::try_from(message),
+        Ok(Borrowed {
+            value: &1,
+            other: "other",
+        })
+    );
+    let message: Message<'_, '_, u32, u16> = Message::Converted { value: 1 };
+    let converted = Converted::try_from(message);
+    assert_eq!(converted, Ok(Converted { value: 1 }));
+}
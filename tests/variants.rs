@@ -0,0 +1,82 @@
+#![allow(dead_code)]
+#[macro_use]
+extern crate derive_more;
+
+#[derive(Debug, PartialEq, Variants)]
+enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+#[derive(Debug, PartialEq, Variants)]
+#[variants(rename_all = "kebab-case")]
+enum Status {
+    InProgress,
+    HTTPError,
+    #[variants(rename = "ok")]
+    Done,
+}
+
+#[derive(Variants)]
+#[variants(rename_all = "camelCase")]
+enum Mode {
+    ReadOnly,
+    ReadWrite,
+}
+
+#[derive(Variants)]
+#[variants(rename_all = "SCREAMING_SNAKE_CASE")]
+enum Event<T> {
+    KeyPress(char),
+    MouseClick { x: i32, y: i32 },
+    Custom(T),
+    Quit,
+}
+
+#[derive(Variants)]
+#[allow(non_camel_case_types)]
+enum Keyword {
+    r#type,
+    r#match,
+}
+
+const SOUTH: Option<Direction> = Direction::from_index(2);
+
+#[test]
+fn variant_name() {
+    assert_eq!(Direction::North.variant_name(), "North");
+    assert_eq!(Status::InProgress.variant_name(), "in-progress");
+    assert_eq!(Status::HTTPError.variant_name(), "http-error");
+    assert_eq!(Status::Done.variant_name(), "ok");
+    assert_eq!(Mode::ReadOnly.variant_name(), "readOnly");
+    assert_eq!(Event::KeyPress::<()>('a').variant_name(), "KEY_PRESS");
+    assert_eq!(
+        Event::MouseClick::<()> { x: 1, y: 2 }.variant_name(),
+        "MOUSE_CLICK"
+    );
+    assert_eq!(Event::Custom(()).variant_name(), "CUSTOM");
+    assert_eq!(Keyword::r#type.variant_name(), "type");
+}
+
+#[test]
+fn fieldless() {
+    assert_eq!(Direction::COUNT, 4);
+    assert_eq!(
+        Direction::VARIANTS,
+        &[
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West
+        ]
+    );
+    for (i, direction) in Direction::VARIANTS.iter().enumerate() {
+        assert_eq!(direction.index(), i);
+        assert_eq!(Direction::from_index(i).as_ref(), Some(direction));
+    }
+    assert_eq!(Direction::from_index(4), None);
+    assert_eq!(SOUTH, Some(Direction::South));
+    assert_eq!(Status::COUNT, 3);
+}